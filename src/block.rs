@@ -1,4 +1,4 @@
-use crate::{tokens, Ty};
+use crate::tokens;
 
 ast_struct! {
     pub struct Block {
@@ -7,33 +7,63 @@ ast_struct! {
     }
 }
 
+use crate::directive::{self, Conditional, Define, Directive, DirectiveItem, Include};
+use crate::pragma::Pragma;
+use crate::static_assert::StaticAssert;
 use crate::{Declr, Stmnt};
 
 ast_enum! {
     pub enum BlockItem {
         Stmnt(Stmnt),
         Declr(Declr),
+        StaticAssert(StaticAssert),
+        Conditional(Conditional<BlockItem>),
+        Define(Define),
+        Include(Include),
+        Pragma(Pragma),
+        Directive(Directive),
     }
 }
 
-use crate::{Parse, ParseStream, Result};
+use crate::specifier::peek_specifier;
+use crate::{Ident, Parse, ParseStream, Result};
 
 impl Parse for Block {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
         let brace = braced!(content in parse)?;
-        let mut items = vec![];
-        while !content.is_empty() {
-            items.push(content.parse()?)
-        }
+        let items = content.scoped(|content| {
+            let mut items = vec![];
+            while !content.is_empty() {
+                items.push(content.parse()?)
+            }
+            Ok(items)
+        })?;
         Ok(Self { brace, items })
     }
 }
 
 impl Parse for BlockItem {
     fn parse(parse: ParseStream) -> Result<Self> {
-        Ok(if parse.fork().expect::<Ty>() {
-            Self::Declr(parse.parse()?)
+        if let Some(directive) = directive::peek_item(parse)? {
+            return Ok(match directive {
+                DirectiveItem::Conditional => Self::Conditional(parse.parse()?),
+                DirectiveItem::Define => Self::Define(parse.parse()?),
+                DirectiveItem::Include => Self::Include(parse.parse()?),
+                DirectiveItem::Pragma => Self::Pragma(parse.parse()?),
+                DirectiveItem::Other => Self::Directive(parse.parse()?),
+            });
+        }
+        Ok(if parse.peek::<token![_Static_assert]>() {
+            Self::StaticAssert(parse.parse()?)
+        } else if parse.peek::<Ident>() && parse.peek2::<token![:]>() {
+            // labels have their own name space, `T:` is one even if `T` is a
+            // typedef name
+            Self::Stmnt(parse.parse()?)
+        } else if peek_specifier(parse) {
+            let declr: Declr = parse.parse()?;
+            declr.declare(parse);
+            Self::Declr(declr)
         } else {
             Self::Stmnt(parse.parse()?)
         })
@@ -56,6 +86,12 @@ mod quote {
             match self {
                 BlockItem::Stmnt(stmnt) => stmnt.to_tokens(tokens),
                 BlockItem::Declr(declr) => declr.to_tokens(tokens),
+                BlockItem::StaticAssert(e) => e.to_tokens(tokens),
+                BlockItem::Conditional(e) => e.to_tokens(tokens),
+                BlockItem::Define(e) => e.to_tokens(tokens),
+                BlockItem::Include(e) => e.to_tokens(tokens),
+                BlockItem::Pragma(e) => e.to_tokens(tokens),
+                BlockItem::Directive(e) => e.to_tokens(tokens),
             }
         }
     }
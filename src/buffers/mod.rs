@@ -1,11 +1,12 @@
-mod parse;
+pub(crate) mod parse;
 
-use crate::error::Pos;
-use crate::parse::{Parse, ParseStream};
+use crate::parse::{Parse, ParseConfig, ParseStream};
 use crate::peek::Lookahead;
 use crate::tokens::{Delimeter, Literal, Punct, TokenCell, TokenTree};
 use crate::{Error, Peek, Result};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::{fmt::Display, marker::PhantomData};
 
 #[derive(Clone, Debug)]
@@ -15,22 +16,43 @@ pub struct TokenStream {
 
 impl Display for TokenStream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(
-            &self
-                .entries
-                .iter()
-                .fold(String::new(), |acc, n| format!("{acc}{} ", n.tt)),
-        )
+        let mut out = String::new();
+        for cell in self.entries.iter() {
+            if let TokenTree::Directive(_) = cell.tt {
+                // directives always take up a line of their own
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str(&format!("{}\n", cell.tt));
+            } else if cell.joint() {
+                out.push_str(&cell.tt.to_string());
+            } else {
+                out.push_str(&format!("{} ", cell.tt));
+            }
+        }
+        f.write_str(&out)
     }
 }
 
 impl TokenStream {
     pub fn from_str(str: &str) -> Result<Self> {
         Ok(Self {
-            entries: parse::parse_str(str)?,
+            entries: parse::parse_str(str, false)?,
+        })
+    }
+
+    /// Like [`TokenStream::from_str`], but keeps comments around as
+    /// [`TokenTree::Comment`] entries, which cursors skip over.
+    pub fn from_str_with_comments(str: &str) -> Result<Self> {
+        Ok(Self {
+            entries: parse::parse_str(str, true)?,
         })
     }
 
+    pub(crate) fn cells(&self) -> &[TokenCell] {
+        &self.entries
+    }
+
     pub(crate) fn new_empty() -> Self {
         Self {
             entries: Box::new([]),
@@ -57,12 +79,34 @@ impl TokenStream {
         self.entries = new.into_boxed_slice();
     }
 
+    /// Glues the last token to whatever comes after it when printing, as in
+    /// the name and parameters of `#define F(x)`.
+    pub fn set_joint(&mut self) {
+        if let Some(last) = self.entries.last_mut() {
+            last.set_joint()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
     pub fn parse<'a, P: Parse>(&'a self) -> ::core::result::Result<P, Error<'a>>
+    where
+        Self: 'a,
+    {
+        self.parse_with_config(ParseConfig::default())
+    }
+
+    pub fn parse_with_config<'a, P: Parse>(
+        &'a self,
+        config: ParseConfig,
+    ) -> ::core::result::Result<P, Error<'a>>
     where
         Self: 'a,
     {
         let cursor = self.cursor();
-        let parse_buffer = ParseBuffer::new(cursor);
+        let parse_buffer = ParseBuffer::new_with_config(cursor, config);
         P::parse(&parse_buffer)
     }
 
@@ -90,6 +134,7 @@ impl<'a> Cursor<'a> {
             end: pos.add(len),
             marker: Default::default(),
         }
+        .skip_comments()
     }
 
     pub fn from_token_stream(ts: &'a TokenStream) -> Self {
@@ -108,6 +153,14 @@ impl<'a> Cursor<'a> {
             pos: unsafe { self.pos.add(1) },
             ..self
         }
+        .skip_comments()
+    }
+
+    fn skip_comments(mut self) -> Self {
+        while let Some(TokenTree::Comment(_)) = self.entry() {
+            self.pos = unsafe { self.pos.add(1) };
+        }
+        self
     }
 
     fn entry_cell(self) -> Option<&'a TokenCell> {
@@ -163,6 +216,25 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    /// Whether the current token is directly followed by the next one,
+    /// without any whitespace in between.
+    pub(crate) fn joined_to_next(self) -> bool {
+        let Some(cell) = self.entry_cell() else {
+            return false;
+        };
+        let next = self.next().entry_cell().and_then(|next| next.pos());
+        cell.joint()
+            || matches!((cell.pos(), next), (Some(pos), Some(next))
+                if pos.row == next.row && pos.col + cell.tt.to_string().chars().count() == next.col)
+    }
+
+    pub fn directive(self) -> Option<(&'a TokenStream, Cursor<'a>)> {
+        match &self.entry()? {
+            TokenTree::Directive(entries) => Some((entries, self.next())),
+            _ => None,
+        }
+    }
+
     pub fn group(self, delim: Delimeter) -> Option<(&'a TokenStream, Cursor<'a>)> {
         match &self.entry()? {
             TokenTree::Group(cmp, entries) if *cmp == delim => Some((entries, self.next())),
@@ -198,8 +270,8 @@ impl<'a> Cursor<'a> {
     pub fn error(self, err: impl Display) -> Error<'a> {
         let entry = self.entry_cell();
         if let Some(entry) = entry {
-            if let Some(Pos { col, row }) = entry.pos() {
-                Error::new_with_pos(err, Some(self), None, col, row)
+            if let Some(pos) = entry.pos() {
+                Error::new_at(err, Some(self), pos)
             } else {
                 Error::new(err, Some(self))
             }
@@ -209,27 +281,122 @@ impl<'a> Cursor<'a> {
     }
 }
 
+/// State shared between a buffer, its forks and the buffers of the groups
+/// inside of it.
+struct ParseState {
+    config: ParseConfig,
+    scopes: RefCell<Scopes>,
+    /// Number of enclosing `switch` bodies.
+    switches: Cell<usize>,
+}
+
+/// Ordinary identifiers declared in each scope, innermost last, mapped to
+/// whether they name a type. An inner declaration shadows a typedef name of
+/// an outer scope.
+#[derive(Debug)]
+struct Scopes {
+    scopes: Vec<HashMap<String, bool>>,
+}
+
+impl Scopes {
+    fn new(config: &ParseConfig) -> Self {
+        let file = config
+            .typedef_names
+            .iter()
+            .map(|name| (name.clone(), true))
+            .collect();
+        Self { scopes: vec![file] }
+    }
+
+    fn is_typedef_name(&self, ident: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(ident))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
 pub struct ParseBuffer<'a> {
     cursor: Cell<Cursor<'static>>,
+    state: Rc<ParseState>,
     mark: PhantomData<Cursor<'a>>,
 }
 
 impl<'a> ParseBuffer<'a> {
-    pub(crate) fn new(cursor: Cursor<'a>) -> Self {
+    pub(crate) fn new_with_config(cursor: Cursor<'a>, config: ParseConfig) -> Self {
+        ParseBuffer {
+            cursor: Cell::new(Cursor::from_cursor(cursor)),
+            state: Rc::new(ParseState {
+                scopes: RefCell::new(Scopes::new(&config)),
+                switches: Cell::new(0),
+                config,
+            }),
+            mark: Default::default(),
+        }
+    }
+
+    /// Creates a buffer for the contents of a group, sharing the state with
+    /// this one.
+    pub(crate) fn nested<'b>(&self, cursor: Cursor<'b>) -> ParseBuffer<'b> {
         ParseBuffer {
             cursor: Cell::new(Cursor::from_cursor(cursor)),
+            state: self.state.clone(),
             mark: Default::default(),
         }
     }
 
+    pub fn config(&self) -> &ParseConfig {
+        &self.state.config
+    }
+
     pub fn is_empty(&self) -> bool {
         self.cursor().is_empty()
     }
 
+    /// Whether `ident` names a type in the current scope, as declared by a
+    /// `typedef` or [`ParseConfig::typedef_names`].
+    pub fn is_typedef_name(&self, ident: &str) -> bool {
+        self.state.scopes.borrow().is_typedef_name(ident)
+    }
+
+    /// Declares `ident` in the innermost scope, as a typedef name or as an
+    /// ordinary identifier hiding a typedef name of an outer scope.
+    pub(crate) fn declare(&self, ident: &str, typedef: bool) {
+        let mut scopes = self.state.scopes.borrow_mut();
+        if let Some(scope) = scopes.scopes.last_mut() {
+            scope.insert(ident.to_string(), typedef);
+        }
+    }
+
+    /// Parses `f` in a new scope, for blocks and function definitions.
+    pub(crate) fn scoped<P>(&self, f: impl FnOnce(ParseStream) -> Result<P>) -> Result<P> {
+        self.state.scopes.borrow_mut().scopes.push(HashMap::new());
+        let result = f(self);
+        self.state.scopes.borrow_mut().scopes.pop();
+        result
+    }
+
+    /// Parses `f` as the body of a `switch`, where `case` and `default`
+    /// labels are allowed.
+    pub(crate) fn switch_body<P>(&self, f: impl FnOnce(ParseStream) -> Result<P>) -> Result<P> {
+        let switches = &self.state.switches;
+        switches.set(switches.get() + 1);
+        let result = f(self);
+        switches.set(switches.get() - 1);
+        result
+    }
+
+    pub(crate) fn in_switch(&self) -> bool {
+        self.state.switches.get() > 0
+    }
+
     pub fn fork(&self) -> Self {
         Self {
             cursor: self.cursor.clone(),
-            ..*self
+            state: self.state.clone(),
+            mark: Default::default(),
         }
     }
 
@@ -1,72 +1,130 @@
+use crate::error::{Pos, SourceFile};
 use crate::tokens::{self, Literal, Punct, TokenCell};
 use crate::tokens::{Delimeter, TokenTree};
 use crate::{Error, Result, TokenStream};
 use peeking_take_while::PeekableExt;
 use std::cell::Cell;
+use std::path::PathBuf;
+use std::rc::Rc;
 
-enum ParsedTy {
+#[derive(Clone, Debug)]
+pub(crate) enum ParsedTy {
     Ident(String),
     Literal(Literal),
     Punct(Punct),
     Group(Delimeter),
     End(Delimeter),
+    Comment(String),
 }
 
-struct Parsed {
+/// A token as lexed, before groups are formed. This is what the
+/// preprocessor works on.
+#[derive(Clone, Debug)]
+pub(crate) struct Parsed {
+    pub col: usize,
+    pub row: usize,
+    /// First token on its line.
+    pub bol: bool,
+    /// Preceded by whitespace or a comment.
+    pub space: bool,
+    pub file: Option<Rc<SourceFile>>,
+    pub ty: ParsedTy,
+}
+
+impl Parsed {
+    pub fn pos(&self) -> Pos {
+        Pos {
+            col: self.col,
+            row: self.row,
+            file: self.file.clone(),
+        }
+    }
+}
+
+/// A lexing error, together with everything lexed before it so the position
+/// can be mapped through any preceding line markers.
+struct LexError {
+    parsed: Vec<Parsed>,
+    msg: String,
     col: usize,
     row: usize,
-    ty: ParsedTy,
 }
 
-fn split(mut iter: impl Iterator<Item = char>) -> Result<Vec<Parsed>> {
+fn split(
+    chars: impl Iterator<Item = char>,
+    keep_comments: bool,
+) -> std::result::Result<Vec<Parsed>, LexError> {
     let mut vec = vec![];
-    let col_initial = 1;
-    let row_initial = 1;
-    let col_old = Cell::new(col_initial);
-    let row_old = Cell::new(row_initial);
-    let col = Cell::new(col_initial);
-    let row = Cell::new(row_initial);
-    let mut iter = std::iter::from_fn(|| {
-        let c = iter.next();
-        c.map(|c| {
-            match c {
-                '\n' => {
-                    col.set(0);
-                    row.set(row.get() + 1)
-                }
-                _ => col.set(col.get() + 1),
-            };
-            c
-        })
+    let col_old = Cell::new(1);
+    let row_old = Cell::new(1);
+    let col = Cell::new(0);
+    let row = Cell::new(1);
+    // index of the character a token starts at, of the start of the previous
+    // token and of the last newline before the token, used to find the first
+    // token on a line.
+    let read = Cell::new(0);
+    let start = Cell::new(0);
+    let prev_start = Cell::new(None);
+    let newline = Cell::new(None);
+    let start_newline = Cell::new(None);
+    let space = Cell::new(false);
+    let mut chars = chars.peekable();
+    let mut iter = std::iter::from_fn(|| loop {
+        let c = chars.next()?;
+        if c == '\\' && chars.peek() == Some(&'\n') {
+            // line splice, the next line continues the current one
+            chars.next();
+            col.set(0);
+            row.set(row.get() + 1);
+            continue;
+        }
+        match c {
+            '\n' => {
+                col.set(0);
+                row.set(row.get() + 1);
+                newline.set(Some(read.get()));
+            }
+            _ => col.set(col.get() + 1),
+        };
+        read.set(read.get() + 1);
+        break Some(c);
     })
     .peekable();
     macro_rules! p {
         ($e:expr) => {{
-            let c = col_old.get();
-            let r = row_old.get();
-            col_old.set(col.get());
-            row_old.set(row.get());
+            let bol = prev_start
+                .get()
+                .map_or(true, |prev| start_newline.get().is_some_and(|nl| nl > prev));
+            prev_start.set(Some(start.get()));
             vec.push(Parsed {
-                col: c,
-                row: r,
+                col: col_old.get(),
+                row: row_old.get(),
+                bol,
+                space: space.replace(false),
+                file: None,
                 ty: $e,
             })
         }};
     }
     macro_rules! e {
         ($e:expr) => {
-            Err(Error::new_with_pos(
-                $e,
-                None,
-                None,
-                col_old.get(),
-                row_old.get(),
-            ))
+            Err(LexError {
+                parsed: vec,
+                msg: $e.to_string(),
+                col: col_old.get(),
+                row: row_old.get(),
+            })
         };
     }
     while let Some(peek) = iter.peek() {
+        // the peeked character was just read from the underlying iterator,
+        // so `col` and `row` currently point at it.
+        col_old.set(col.get());
+        row_old.set(row.get());
+        start.set(read.get() - 1);
+        start_newline.set(newline.get());
         match peek {
-            c if c.is_alphabetic() => {
+            c if c.is_alphabetic() || *c == '_' => {
                 let iter = iter.by_ref().peeking_take_while(|c| match c {
                     c if c.is_alphanumeric() => true,
                     '_' => true,
@@ -75,16 +133,21 @@ fn split(mut iter: impl Iterator<Item = char>) -> Result<Vec<Parsed>> {
                 let ident: String = iter.collect();
                 p!(ParsedTy::Ident(ident))
             }
-            c if c.is_numeric() => {
-                let iter = iter.by_ref().peeking_take_while(|c| match c {
-                    c if c.is_numeric() => true,
-                    '.' => true,
-                    _ => false,
+            c if c.is_ascii_digit() => {
+                // a preprocessing number, exponents may be signed
+                let mut prev = ' ';
+                let iter = iter.by_ref().peeking_take_while(|&c| {
+                    let take = c.is_alphanumeric()
+                        || matches!(c, '_' | '.')
+                        || matches!((prev, c), ('e' | 'E' | 'p' | 'P', '+' | '-'));
+                    prev = c;
+                    take
                 });
                 let number: String = iter.collect();
-                p!(ParsedTy::Literal(Literal::Int(
-                    number.parse::<i128>().unwrap()
-                )))
+                match number_literal(&number) {
+                    Some(lit) => p!(ParsedTy::Literal(lit)),
+                    None => return e!(format!("invalid number '{number}'")),
+                }
             }
             '(' => {
                 iter.next();
@@ -112,11 +175,14 @@ fn split(mut iter: impl Iterator<Item = char>) -> Result<Vec<Parsed>> {
             }
             '\"' => {
                 let mut found = false;
+                let mut escaped = false;
                 iter.next();
+                // escape sequences are kept as written
                 let string: String = iter
                     .by_ref()
                     .take_while(|&c| {
-                        found = c == '\"';
+                        found = c == '\"' && !escaped;
+                        escaped = c == '\\' && !escaped;
                         !found
                     })
                     .collect();
@@ -126,12 +192,45 @@ fn split(mut iter: impl Iterator<Item = char>) -> Result<Vec<Parsed>> {
                     return e!("missing closing '\"' for string literal");
                 }
             }
+            '\'' => {
+                let mut found = false;
+                let mut escaped = false;
+                iter.next();
+                let char: String = iter
+                    .by_ref()
+                    .take_while(|&c| {
+                        found = c == '\'' && !escaped;
+                        escaped = c == '\\' && !escaped;
+                        !found && c != '\n'
+                    })
+                    .collect();
+                if found && !char.is_empty() {
+                    p!(ParsedTy::Literal(Literal::Char(char)))
+                } else {
+                    return e!("missing closing \"'\" for character constant");
+                }
+            }
+            '<' if after_include(&vec) => {
+                iter.next();
+                let mut found = false;
+                let name: String = iter
+                    .by_ref()
+                    .take_while(|&c| {
+                        found = c == '>';
+                        !found && c != '\n'
+                    })
+                    .collect();
+                if found {
+                    p!(ParsedTy::Literal(Literal::Header(name)))
+                } else {
+                    return e!("missing closing '>' for header name");
+                }
+            }
             c if c.is_whitespace() => {
                 iter.by_ref()
                     .peeking_take_while(|c| c.is_whitespace())
                     .for_each(|_| {});
-                col_old.set(col.get());
-                row_old.set(row.get());
+                space.set(true);
             }
             _ => {
                 let mut punct = String::new();
@@ -139,6 +238,18 @@ fn split(mut iter: impl Iterator<Item = char>) -> Result<Vec<Parsed>> {
                 'exit: {
                     while let Some(next) = iter.peek() {
                         punct.push(*next);
+                        if punct == "//" || punct == "/*" {
+                            iter.next();
+                            let Some(comment) = lex_comment(&mut iter, punct == "/*") else {
+                                return e!("missing closing '*/' for block comment");
+                            };
+                            punct.push_str(&comment);
+                            if keep_comments {
+                                p!(ParsedTy::Comment(punct));
+                            }
+                            space.set(true);
+                            break 'exit;
+                        }
                         match tokens::match_punct(&punct) {
                             tokens::PunctMatch::Matched(matched) => {
                                 cur_punct = Some(matched);
@@ -169,53 +280,293 @@ fn split(mut iter: impl Iterator<Item = char>) -> Result<Vec<Parsed>> {
     Ok(vec)
 }
 
-pub(super) fn parse_str(str: &str) -> Result<Box<[TokenCell]>> {
-    let split = split(str.chars())?;
-    parsed_into_boxed_entries(split)
+/// Converts a number with an optional radix prefix and suffix, keeping its
+/// spelling.
+fn number_literal(number: &str) -> Option<Literal> {
+    let spelling = number.to_string();
+    let lower = number.to_ascii_lowercase();
+    let (digits, radix) = if let Some(hex) = lower.strip_prefix("0x") {
+        (hex, 16)
+    } else if let Some(bin) = lower.strip_prefix("0b") {
+        (bin, 2)
+    } else {
+        (lower.as_str(), 10)
+    };
+    if radix == 10 && digits.contains(['.', 'e']) {
+        return digits
+            .trim_end_matches(['f', 'l'])
+            .parse()
+            .ok()
+            .map(|value| Literal::Float(value, spelling));
+    }
+    let trimmed = digits.trim_end_matches(['u', 'l']);
+    let unsigned = digits[trimmed.len()..].contains('u');
+    let radix = match radix {
+        10 if trimmed.len() > 1 && trimmed.starts_with('0') => 8,
+        radix => radix,
+    };
+    let value = i128::from_str_radix(trimmed, radix).ok()?;
+    Some(if unsigned {
+        Literal::UInt(value, spelling)
+    } else {
+        Literal::Int(value, spelling)
+    })
 }
 
-fn parsed_into_boxed_entries(parsed: Vec<Parsed>) -> Result<Box<[TokenCell]>> {
+/// Header names (`<stdio.h>`) are only lexed as such right after `#include`.
+fn after_include(parsed: &[Parsed]) -> bool {
+    match parsed {
+        [.., hash, Parsed {
+            ty: ParsedTy::Ident(include),
+            ..
+        }] => {
+            hash.bol
+                && matches!(hash.ty, ParsedTy::Punct(Punct::Hashtag(_)))
+                && matches!(include.as_str(), "include" | "include_next")
+        }
+        _ => false,
+    }
+}
+
+/// Consumes the body of a comment whose opening `//` or `/*` has already been
+/// read, returning the remaining text including the closing `*/`. Line
+/// comments stop before the terminating newline.
+fn lex_comment(
+    iter: &mut std::iter::Peekable<impl Iterator<Item = char>>,
+    block: bool,
+) -> Option<String> {
+    let mut body = String::new();
+    if block {
+        for c in iter.by_ref() {
+            body.push(c);
+            if body.ends_with("*/") {
+                return Some(body);
+            }
+        }
+        None
+    } else {
+        body.extend(iter.peeking_take_while(|&c| c != '\n'));
+        Some(body)
+    }
+}
+
+/// Tracks the logical file and line set by gcc line markers
+/// (`# 42 "foo.h" 1 3`) and `#line` directives.
+#[derive(Default)]
+struct LineMarkers {
+    file: Option<Rc<SourceFile>>,
+    /// Physical row of the last marker, and the logical row of the line after it.
+    marker: Option<(usize, usize)>,
+}
+
+impl LineMarkers {
+    fn pos(&self, col: usize, row: usize) -> Pos {
+        Pos {
+            col,
+            row: self.row(row),
+            file: self.file.clone(),
+        }
+    }
+
+    fn row(&self, row: usize) -> usize {
+        match self.marker {
+            Some((marker_row, line_nr)) => row.saturating_sub(marker_row + 1) + line_nr,
+            None => row,
+        }
+    }
+
+    fn relocate(&self, mut parsed: Parsed) -> Parsed {
+        parsed.row = self.row(parsed.row);
+        parsed.file = self.file.clone();
+        parsed
+    }
+
+    /// Removes the line markers from `parsed`, rewriting the rows and files
+    /// of the tokens following them.
+    fn apply(&mut self, parsed: Vec<Parsed>) -> Result<Vec<Parsed>> {
+        let mut vec = Vec::with_capacity(parsed.len());
+        let mut iter = parsed.into_iter().peekable();
+        while let Some(next) = iter.next() {
+            if !next.bol || !matches!(next.ty, ParsedTy::Punct(Punct::Hashtag(_))) {
+                vec.push(self.relocate(next));
+                continue;
+            }
+            let row = next.row;
+            let line: Vec<_> = iter.by_ref().peeking_take_while(|p| !p.bol).collect();
+            match parse_line_marker(&line) {
+                Ok(Some(marker)) => self.set(row, marker),
+                Ok(None) => {
+                    vec.push(self.relocate(next));
+                    vec.extend(line.into_iter().map(|p| self.relocate(p)));
+                }
+                Err(msg) => return Err(Error::new_at(msg, None, self.pos(next.col, row))),
+            }
+        }
+        Ok(vec)
+    }
+
+    fn set(&mut self, row: usize, marker: LineMarker) {
+        let current = self.file.as_deref();
+        let path = marker
+            .path
+            .map(PathBuf::from)
+            .or_else(|| current.map(|file| file.path.clone()));
+        let depth = current.map_or(0, |file| file.include_depth);
+        let include_depth = match marker.flags {
+            Some(LineMarkerFlags { enter: true, .. }) => depth + 1,
+            Some(LineMarkerFlags { leave: true, .. }) => depth.saturating_sub(1),
+            _ => depth,
+        };
+        let system_header = match marker.flags {
+            Some(flags) => flags.system_header,
+            None => current.is_some_and(|file| file.system_header),
+        };
+        self.file = path.map(|path| {
+            Rc::new(SourceFile {
+                path,
+                system_header,
+                include_depth,
+            })
+        });
+        self.marker = Some((row, marker.line));
+    }
+}
+
+struct LineMarker {
+    line: usize,
+    path: Option<String>,
+    /// Only present for the gcc form.
+    flags: Option<LineMarkerFlags>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct LineMarkerFlags {
+    enter: bool,
+    leave: bool,
+    system_header: bool,
+}
+
+/// Parses the tokens following a `#` at the start of a line, returning `None`
+/// if they don't form a line marker.
+fn parse_line_marker(line: &[Parsed]) -> std::result::Result<Option<LineMarker>, &'static str> {
+    let (mut flags, rest) = match line.first().map(|p| &p.ty) {
+        Some(ParsedTy::Literal(Literal::Int(..))) => (Some(LineMarkerFlags::default()), line),
+        Some(ParsedTy::Ident(ident)) if ident == "line" => (None, &line[1..]),
+        _ => return Ok(None),
+    };
+    let mut rest = rest.iter().map(|p| &p.ty);
+    let line = match rest.next() {
+        Some(ParsedTy::Literal(Literal::Int(int, _))) if *int >= 0 => *int as usize,
+        _ => return Err("expected line number in line directive"),
+    };
+    let path = match rest.next() {
+        Some(ParsedTy::Literal(Literal::Str(str))) => Some(str.clone()),
+        None => None,
+        _ => return Err("expected file name in line directive"),
+    };
+    for flag in rest {
+        match (flag, flags.as_mut()) {
+            (ParsedTy::Literal(Literal::Int(1, _)), Some(flags)) => flags.enter = true,
+            (ParsedTy::Literal(Literal::Int(2, _)), Some(flags)) => flags.leave = true,
+            (ParsedTy::Literal(Literal::Int(3, _)), Some(flags)) => flags.system_header = true,
+            (ParsedTy::Literal(Literal::Int(4, _)), Some(_)) => {}
+            _ => return Err("invalid flag in line marker"),
+        }
+    }
+    Ok(Some(LineMarker { line, path, flags }))
+}
+
+pub(super) fn parse_str(str: &str, keep_comments: bool) -> Result<Box<[TokenCell]>> {
+    parsed_into_boxed_entries(lex(str, keep_comments)?)
+}
+
+/// Lexes `str` into flat tokens, with any line markers applied.
+pub(crate) fn lex(str: &str, keep_comments: bool) -> Result<Vec<Parsed>> {
+    let mut markers = LineMarkers::default();
+    match split(str.chars(), keep_comments) {
+        Ok(split) => markers.apply(split),
+        Err(LexError {
+            mut parsed,
+            msg,
+            col,
+            row,
+        }) => {
+            // The line holding the error is incomplete, so it can't be a marker.
+            parsed.retain(|p| p.row < row);
+            markers.apply(parsed)?;
+            Err(Error::new_at(msg, None, markers.pos(col, row)))
+        }
+    }
+}
+
+/// Lexes `str`, which has to form exactly one token. Used when pasting
+/// tokens together.
+pub(crate) fn lex_one(str: &str) -> Option<ParsedTy> {
+    let mut parsed = split(str.chars(), false).ok()?.into_iter();
+    match (parsed.next(), parsed.next()) {
+        (Some(parsed), None) if !matches!(parsed.ty, ParsedTy::Comment(_)) => Some(parsed.ty),
+        _ => None,
+    }
+}
+
+pub(crate) fn parsed_into_boxed_entries(parsed: Vec<Parsed>) -> Result<Box<[TokenCell]>> {
+    /// Returns the entries up to the closing `group_end` delimiter, together
+    /// with the position of said delimiter. Inside a directive the entries
+    /// end at the next line.
     fn into_boxed(
-        iter: &mut impl Iterator<Item = Parsed>,
+        iter: &mut std::iter::Peekable<impl Iterator<Item = Parsed>>,
         group_end: Option<Delimeter>,
-    ) -> Result<Box<[TokenCell]>> {
+        directive: bool,
+    ) -> Result<(Box<[TokenCell]>, Option<Pos>)> {
         let mut vec = vec![];
-        while let Some(next) = iter.next() {
-            vec.push(TokenCell::new_with_pos(
-                match next.ty {
-                    ParsedTy::Ident(ident) => TokenTree::Ident(ident),
-                    ParsedTy::Literal(lit) => TokenTree::Literal(lit),
-                    ParsedTy::Punct(punct) => TokenTree::Punct(punct),
-                    ParsedTy::Group(group) => {
-                        TokenTree::Group(group, TokenStream::new(into_boxed(iter, Some(group))?))
-                    }
-                    ParsedTy::End(delim) => {
-                        if let Some(group_end) = group_end {
-                            if delim == group_end {
-                                return Ok(vec.into_boxed_slice());
-                            }
-                        }
-                        return Err(Error::new_with_pos(
-                            format!(
-                                "unexpected group delimiter '{}'.",
-                                match delim {
-                                    Delimeter::Paren => ')',
-                                    Delimeter::Brace => '}',
-                                    Delimeter::Bracket => ']',
-                                }
-                            ),
-                            None,
+        while let Some(next) = iter.next_if(|next| !directive || !next.bol) {
+            let mut close = None;
+            let pos = next.pos();
+            let space = next.space;
+            let tt = match next.ty {
+                ParsedTy::Ident(ident) => TokenTree::Ident(ident),
+                ParsedTy::Literal(lit) => TokenTree::Literal(lit),
+                ParsedTy::Punct(Punct::Hashtag(_)) if next.bol => {
+                    TokenTree::Directive(TokenStream::new(into_boxed(iter, None, true)?.0))
+                }
+                ParsedTy::Punct(punct) => TokenTree::Punct(punct),
+                ParsedTy::Comment(comment) => TokenTree::Comment(comment),
+                ParsedTy::Group(group) => {
+                    let (entries, end) = into_boxed(iter, Some(group), directive)?;
+                    if end.is_none() && directive {
+                        return Err(Error::new_at(
+                            "unterminated group in preprocessing directive",
                             None,
-                            next.col,
-                            next.row,
+                            pos,
                         ));
                     }
-                },
-                next.col,
-                next.row,
-            ))
+                    close = end;
+                    TokenTree::Group(group, TokenStream::new(entries))
+                }
+                ParsedTy::End(delim) => {
+                    if group_end == Some(delim) {
+                        return Ok((vec.into_boxed_slice(), Some(pos)));
+                    }
+                    return Err(Error::new_at(
+                        format!(
+                            "unexpected group delimiter '{}'.",
+                            match delim {
+                                Delimeter::Paren => ')',
+                                Delimeter::Brace => '}',
+                                Delimeter::Bracket => ']',
+                            }
+                        ),
+                        None,
+                        pos,
+                    ));
+                }
+            };
+            let mut cell = TokenCell::new_with_pos(tt, pos);
+            cell.set_close(close);
+            cell.set_space(space);
+            vec.push(cell)
         }
-        Ok(vec.into_boxed_slice())
+        Ok((vec.into_boxed_slice(), None))
     }
-    into_boxed(&mut parsed.into_iter(), None)
+    Ok(into_boxed(&mut parsed.into_iter().peekable(), None, false)?.0)
 }
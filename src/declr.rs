@@ -1,10 +1,13 @@
 use crate::expr;
-use crate::specifier::TypeQualifierList;
-use crate::{tokens, Expr, FnArgs, FnParams, Ident, Ptr, Punctuated, Ty};
+use crate::func::FnIdentList;
+use crate::initializer::Initializer;
+use crate::specifier::{DeclSpecifiers, TypeQualifierList};
+use crate::{tokens, Expr, FnParams, Ident, Ptr, Punctuated};
 
 ast_struct! {
+    /// `int *a, b[2];`, where only `a` is a pointer.
     pub struct Declr {
-        pub ty: Ty,
+        pub specifiers: DeclSpecifiers,
         pub vars: Punctuated<InitDeclarator, token![,]>,
         pub semi_colon: token![;],
     }
@@ -27,7 +30,7 @@ ast_struct! {
     pub struct InitDeclaratorInit {
         pub declarator: Declarator,
         pub assign_tk: token![=],
-        pub expr: Box<Expr>,
+        pub initializer: Initializer,
     }
 }
 
@@ -50,7 +53,7 @@ ast_enum! {
         Paren(DDParen),
         Bracket(DDBracket),
         Params(DDParams),
-        Args(DDArgs)
+        IdentList(DDIdentList)
     }
 }
 
@@ -65,7 +68,11 @@ ast_struct! {
     pub struct DDBracket {
         pub direct_declarator: Box<DirectDeclarator>,
         pub bracket: tokens::Bracket,
+        /// `int a[static 10]` in a parameter: `a` points to at least 10 elements.
+        pub static_tk: Option<token![static]>,
         pub qualifier_list: Option<TypeQualifierList>,
+        /// `int a[*]` in a prototype: a variable length array of unspecified size.
+        pub star: Option<token![*]>,
         pub assing_expr: Option<Expr>
     }
 }
@@ -78,19 +85,169 @@ ast_struct! {
 }
 
 ast_struct! {
-    pub struct DDArgs{
+    /// The identifier list of an old-style definition, `int f(a, b) int a, b; {}`.
+    pub struct DDIdentList {
         pub direct_declarator: Box<DirectDeclarator>,
-        pub args: FnArgs
+        pub idents: FnIdentList,
     }
 }
 
+impl Declr {
+    /// Whether the declaration is a `typedef`.
+    pub fn is_typedef(&self) -> bool {
+        self.specifiers
+            .items
+            .iter()
+            .any(|specifier| matches!(specifier, Specifier::Storage(StorageClass::Typedef(_))))
+    }
+
+    /// Records the names this declaration introduces in the current scope,
+    /// so the following declarations and expressions can tell typedef names
+    /// from ordinary identifiers.
+    pub(crate) fn declare(&self, parse: ParseStream) {
+        declare_enumerators(&self.specifiers, parse);
+        let typedef = self.is_typedef();
+        for var in self.vars.iter() {
+            parse.declare(var.declarator().ident().as_str(), typedef);
+        }
+    }
+}
+
+/// Enumerators are ordinary identifiers of the scope the enum is defined in.
+pub(crate) fn declare_enumerators(specifiers: &DeclSpecifiers, parse: ParseStream) {
+    for specifier in &specifiers.items {
+        if let Specifier::Type(TypeSpecifier::Enum(enumeration)) = specifier {
+            for (ident, _) in enumeration.enumerators() {
+                parse.declare(ident.as_str(), false);
+            }
+        }
+    }
+}
+
+impl InitDeclarator {
+    pub fn declarator(&self) -> &Declarator {
+        match self {
+            InitDeclarator::Init(init) => &init.declarator,
+            InitDeclarator::Uninit(uninit) => &uninit.declarator,
+        }
+    }
+
+    pub fn initializer(&self) -> Option<&Initializer> {
+        match self {
+            InitDeclarator::Init(init) => Some(&init.initializer),
+            InitDeclarator::Uninit(_) => None,
+        }
+    }
+}
+
+/// One step in building the type of a declared identifier from the type
+/// given by the declaration specifiers.
+#[derive(Clone, Copy)]
+pub enum Derivation<'a> {
+    Pointer(Option<&'a TypeQualifierList>),
+    Array {
+        is_static: bool,
+        qualifier_list: Option<&'a TypeQualifierList>,
+        /// `[*]`, a variable length array of unspecified size.
+        is_star: bool,
+        len: Option<&'a Expr>,
+    },
+    Function(&'a FnParams),
+    /// An old-style function, whose parameters are declared after the
+    /// identifier list.
+    IdentList(&'a FnIdentList),
+}
+
+impl<'a> Derivation<'a> {
+    /// The pointer closest to the identifier applies first.
+    pub(crate) fn push_pointers(ptr: Option<&'a Ptr>, derivations: &mut Vec<Self>) {
+        let mut pointers = vec![];
+        let mut ptr = ptr;
+        while let Some(p) = ptr {
+            pointers.push(Derivation::Pointer(p.qualifier_list.as_ref()));
+            ptr = p.next.as_deref();
+        }
+        derivations.extend(pointers.into_iter().rev());
+    }
+}
+
+impl Declarator {
+    /// The derivations applied to the declared identifier, starting at the
+    /// identifier: `*a[3]` is an array of pointers, `(*a)[3]` a pointer to
+    /// an array.
+    pub fn derivations(&self) -> Vec<Derivation<'_>> {
+        let mut derivations = vec![];
+        self.push_derivations(&mut derivations);
+        derivations
+    }
+
+    fn push_derivations<'a>(&'a self, derivations: &mut Vec<Derivation<'a>>) {
+        self.direct_declarator.push_derivations(derivations);
+        Derivation::push_pointers(self.ptr.as_ref(), derivations);
+    }
+
+    /// Whether the identifier is declared as a function, `f` in
+    /// `int (*f(int))(void)` as opposed to `int (*f)(int)`.
+    pub fn is_function(&self) -> bool {
+        matches!(
+            self.derivations().first(),
+            Some(Derivation::Function(_) | Derivation::IdentList(_))
+        )
+    }
+
+    /// The declared identifier.
+    pub fn ident(&self) -> &Ident {
+        let mut direct = &self.direct_declarator;
+        loop {
+            direct = match direct {
+                DirectDeclarator::Ident(ident) => return ident,
+                DirectDeclarator::Paren(paren) => &paren.declarator.direct_declarator,
+                DirectDeclarator::Bracket(bracket) => &bracket.direct_declarator,
+                DirectDeclarator::Params(params) => &params.direct_declarator,
+                DirectDeclarator::IdentList(list) => &list.direct_declarator,
+            }
+        }
+    }
+}
+
+impl DirectDeclarator {
+    fn push_derivations<'a>(&'a self, derivations: &mut Vec<Derivation<'a>>) {
+        match self {
+            DirectDeclarator::Ident(_) => {}
+            DirectDeclarator::Paren(paren) => paren.declarator.push_derivations(derivations),
+            DirectDeclarator::Bracket(bracket) => {
+                bracket.direct_declarator.push_derivations(derivations);
+                derivations.push(Derivation::Array {
+                    is_static: bracket.static_tk.is_some(),
+                    qualifier_list: bracket.qualifier_list.as_ref(),
+                    is_star: bracket.star.is_some(),
+                    len: bracket.assing_expr.as_ref(),
+                });
+            }
+            DirectDeclarator::Params(params) => {
+                params.direct_declarator.push_derivations(derivations);
+                derivations.push(Derivation::Function(&params.params));
+            }
+            DirectDeclarator::IdentList(list) => {
+                list.direct_declarator.push_derivations(derivations);
+                derivations.push(Derivation::IdentList(&list.idents));
+            }
+        }
+    }
+}
+
+use crate::specifier::{Specifier, StorageClass, TypeSpecifier};
 use crate::{Parse, ParseStream, Result};
 
 impl Parse for Declr {
     fn parse(parse: ParseStream) -> Result<Self> {
         Ok(Self {
-            ty: parse.parse()?,
-            vars: Punctuated::parse_non_terminated(parse)?,
+            specifiers: parse.parse()?,
+            vars: if parse.peek::<token![;]>() {
+                Punctuated::new()
+            } else {
+                Punctuated::parse_non_terminated(parse)?
+            },
             semi_colon: parse.parse()?,
         })
     }
@@ -117,7 +274,7 @@ impl Parse for InitDeclarator {
             Self::Init(InitDeclaratorInit {
                 declarator,
                 assign_tk: parse.parse()?,
-                expr: parse.parse()?,
+                initializer: parse.parse()?,
             })
         } else {
             Self::Uninit(InitDeclaratorUninit { declarator })
@@ -130,7 +287,7 @@ impl Parse for InitDeclaratorInit {
         Ok(Self {
             declarator: parse.parse()?,
             assign_tk: parse.parse()?,
-            expr: parse.parse()?,
+            initializer: parse.parse()?,
         })
     }
 }
@@ -152,6 +309,21 @@ impl Parse for Declarator {
     }
 }
 
+/// Whether the parentheses ahead hold the identifier list of an old-style
+/// function rather than parameter declarations.
+fn peek_ident_list(parse: ParseStream) -> bool {
+    let fork = parse.fork();
+    let ident_list = || -> Result<bool> {
+        let content;
+        parenthesized!(content in &fork)?;
+        Ok(match content.cursor().ident() {
+            Some((ident, _)) => content.peek::<Ident>() && !content.is_typedef_name(ident),
+            None => false,
+        })
+    };
+    ident_list().unwrap_or(false)
+}
+
 impl Parse for DirectDeclarator {
     fn parse(parse: ParseStream) -> Result<Self> {
         let mut declr = if parse.peek::<Ident>() {
@@ -163,18 +335,32 @@ impl Parse for DirectDeclarator {
         };
         Ok({
             loop {
-                if parse.peek::<tokens::Paren>() {
+                if peek_ident_list(parse) {
+                    declr = Self::IdentList(DDIdentList {
+                        direct_declarator: Box::new(declr),
+                        idents: parse.parse()?,
+                    });
+                } else if parse.peek::<tokens::Paren>() {
                     declr = Self::Params(DDParams {
                         direct_declarator: Box::new(declr),
                         params: parse.parse()?,
                     });
                 } else if parse.peek::<tokens::Bracket>() {
                     let content;
+                    let bracket = bracketed!(content in parse)?;
+                    let ArrayLen {
+                        static_tk,
+                        qualifier_list,
+                        star,
+                        len: assing_expr,
+                    } = parse_array_len(&content)?;
                     declr = Self::Bracket(DDBracket {
                         direct_declarator: Box::new(declr),
-                        bracket: bracketed!(content in parse)?,
-                        qualifier_list: content.parse()?,
-                        assing_expr: expr::parsing::parse_assign(&content).ok(),
+                        bracket,
+                        static_tk,
+                        qualifier_list,
+                        star,
+                        assing_expr,
                     });
                 } else {
                     break declr;
@@ -184,13 +370,60 @@ impl Parse for DirectDeclarator {
     }
 }
 
+/// The contents of the brackets of an array declarator, `[static const 10]`
+/// or `[const *]`.
+pub(crate) struct ArrayLen {
+    pub static_tk: Option<token![static]>,
+    pub qualifier_list: Option<TypeQualifierList>,
+    pub star: Option<token![*]>,
+    pub len: Option<Expr>,
+}
+
+pub(crate) fn parse_array_len(content: ParseStream) -> Result<ArrayLen> {
+    let mut static_at = content.cursor();
+    let mut static_tk: Option<token![static]> = content.parse()?;
+    let qualifier_list = content.parse()?;
+    if static_tk.is_none() {
+        static_at = content.cursor();
+        static_tk = content.parse()?;
+    }
+    // a lone `*`, anything following it makes it a dereference
+    let fork = content.fork();
+    let star = match fork.parse::<Option<token![*]>>()? {
+        Some(star) if fork.is_empty() => {
+            content.set(fork);
+            Some(star)
+        }
+        _ => None,
+    };
+    let len = if content.is_empty() {
+        None
+    } else {
+        Some(expr::parsing::parse_assign(content)?)
+    };
+    if static_tk.is_some() && len.is_none() {
+        return Err(static_at.error("expected array size after `static`"));
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `]`"));
+    }
+    Ok(ArrayLen {
+        static_tk,
+        qualifier_list,
+        star,
+        len,
+    })
+}
+
 impl Parse for DDParen {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
-        Ok(Self {
-            paren: parenthesized!(content in parse)?,
-            declarator: content.parse()?,
-        })
+        let paren = parenthesized!(content in parse)?;
+        let declarator = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after declarator"));
+        }
+        Ok(Self { paren, declarator })
     }
 }
 
@@ -205,21 +438,31 @@ impl Parse for DDParams {
 
 impl Parse for DDBracket {
     fn parse(parse: ParseStream) -> Result<Self> {
+        let direct_declarator = parse.parse()?;
         let content;
+        let bracket = bracketed!(content in parse)?;
+        let ArrayLen {
+            static_tk,
+            qualifier_list,
+            star,
+            len: assing_expr,
+        } = parse_array_len(&content)?;
         Ok(Self {
-            direct_declarator: parse.parse()?,
-            bracket: bracketed!(content in parse)?,
-            qualifier_list: content.parse()?,
-            assing_expr: expr::parsing::parse_assign(&content).ok(),
+            direct_declarator,
+            bracket,
+            static_tk,
+            qualifier_list,
+            star,
+            assing_expr,
         })
     }
 }
 
-impl Parse for DDArgs {
+impl Parse for DDIdentList {
     fn parse(parse: ParseStream) -> Result<Self> {
         Ok(Self {
             direct_declarator: parse.parse()?,
-            args: parse.parse()?,
+            idents: parse.parse()?,
         })
     }
 }
@@ -231,11 +474,11 @@ mod quote {
     impl ToTokens for Declr {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let Self {
-                ty,
+                specifiers,
                 vars,
                 semi_colon,
             } = self;
-            ty.to_tokens(tokens);
+            specifiers.to_tokens(tokens);
             vars.to_tokens(tokens);
             semi_colon.to_tokens(tokens);
         }
@@ -263,11 +506,11 @@ mod quote {
             let Self {
                 declarator,
                 assign_tk,
-                expr,
+                initializer,
             } = self;
             declarator.to_tokens(tokens);
             assign_tk.to_tokens(tokens);
-            expr.to_tokens(tokens);
+            initializer.to_tokens(tokens);
         }
     }
 
@@ -296,7 +539,7 @@ mod quote {
                 DirectDeclarator::Paren(e) => e.to_tokens(tokens),
                 DirectDeclarator::Bracket(e) => e.to_tokens(tokens),
                 DirectDeclarator::Params(e) => e.to_tokens(tokens),
-                DirectDeclarator::Args(e) => e.to_tokens(tokens),
+                DirectDeclarator::IdentList(e) => e.to_tokens(tokens),
             }
         }
     }
@@ -316,12 +559,16 @@ mod quote {
             let Self {
                 direct_declarator,
                 bracket: _,
+                static_tk,
                 qualifier_list,
+                star,
                 assing_expr,
             } = self;
             direct_declarator.to_tokens(tokens);
             to_tokens::bracketed(&to_tokens::multiple(|tokens| {
+                static_tk.to_tokens(tokens);
                 qualifier_list.to_tokens(tokens);
+                star.to_tokens(tokens);
                 assing_expr.to_tokens(tokens);
             }))
             .to_tokens(tokens);
@@ -339,14 +586,14 @@ mod quote {
         }
     }
 
-    impl ToTokens for DDArgs {
+    impl ToTokens for DDIdentList {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let Self {
                 direct_declarator,
-                args,
+                idents,
             } = self;
             direct_declarator.to_tokens(tokens);
-            args.to_tokens(tokens);
+            idents.to_tokens(tokens);
         }
     }
 }
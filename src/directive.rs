@@ -0,0 +1,424 @@
+use crate::{tokens, Ident, Punctuated, TokenStream};
+
+ast_struct! {
+    pub struct Conditional<T> {
+        /// The `#if`, `#ifdef` or `#ifndef` branch followed by any `#elif`,
+        /// `#elifdef`, `#elifndef` and `#else` branches.
+        pub branches: Vec<ConditionalBranch<T>>,
+    }
+}
+
+ast_struct! {
+    pub struct ConditionalBranch<T> {
+        pub kind: ConditionalKind,
+        /// Unevaluated, empty for `#else`.
+        pub condition: TokenStream,
+        pub items: Vec<T>,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConditionalKind {
+    If,
+    Ifdef,
+    Ifndef,
+    Elif,
+    Elifdef,
+    Elifndef,
+    Else,
+}
+
+ast_struct! {
+    pub struct Define {
+        pub name: Ident,
+        pub params: Option<DefineParams>,
+        pub body: TokenStream,
+    }
+}
+
+ast_struct! {
+    pub struct DefineParams {
+        pub paren: tokens::Paren,
+        pub params: Punctuated<Ident, token![,]>,
+        /// The name of a GNU `args...` parameter, not part of `params`.
+        pub variadic_name: Option<Ident>,
+        pub variadic: Option<token![...]>,
+    }
+}
+
+ast_struct! {
+    pub struct Include {
+        pub next: bool,
+        pub path: IncludePath,
+    }
+}
+
+ast_enum! {
+    pub enum IncludePath {
+        /// `#include "foo.h"`
+        Quoted(String),
+        /// `#include <foo.h>`
+        Angled(String),
+        /// `#include FOO_H`
+        Macro(TokenStream),
+    }
+}
+
+ast_struct! {
+    /// Any other directive, kept as is.
+    pub struct Directive {
+        /// `None` for the null directive, a lone `#`.
+        pub name: Option<Ident>,
+        pub tokens: TokenStream,
+    }
+}
+
+impl ConditionalKind {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "if" => Self::If,
+            "ifdef" => Self::Ifdef,
+            "ifndef" => Self::Ifndef,
+            "elif" => Self::Elif,
+            "elifdef" => Self::Elifdef,
+            "elifndef" => Self::Elifndef,
+            "else" => Self::Else,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Ifdef => "ifdef",
+            Self::Ifndef => "ifndef",
+            Self::Elif => "elif",
+            Self::Elifdef => "elifdef",
+            Self::Elifndef => "elifndef",
+            Self::Else => "else",
+        }
+    }
+
+    fn opens(self) -> bool {
+        matches!(self, Self::If | Self::Ifdef | Self::Ifndef)
+    }
+}
+
+use crate::buffers::{Cursor, ParseBuffer};
+use crate::tokens::Literal;
+use crate::{Parse, ParseStream, Result};
+
+/// What an item or block item starting with a directive should be parsed as.
+pub(crate) enum DirectiveItem {
+    Conditional,
+    Define,
+    Include,
+    Pragma,
+    Other,
+}
+
+/// Classifies the directive at the cursor. Returns `None` if there is none or
+/// directives aren't preserved, and an error for the directives that only
+/// continue or close a conditional. Pragmas are always kept.
+pub(crate) fn peek_item(parse: ParseStream) -> Result<Option<DirectiveItem>> {
+    let Some(name) = peek_name(parse) else {
+        return Ok(None);
+    };
+    if name == "pragma" {
+        return Ok(Some(DirectiveItem::Pragma));
+    }
+    if !parse.config().preserve_directives {
+        return Ok(None);
+    }
+    Ok(Some(match name.as_str() {
+        "if" | "ifdef" | "ifndef" => DirectiveItem::Conditional,
+        "elif" | "elifdef" | "elifndef" | "else" | "endif" => {
+            return Err(parse.error(format!("unexpected #{name}")))
+        }
+        "define" => DirectiveItem::Define,
+        "include" | "include_next" => DirectiveItem::Include,
+        _ => DirectiveItem::Other,
+    }))
+}
+
+/// Name of the directive at the cursor, empty for the null directive.
+fn peek_name(parse: ParseStream) -> Option<String> {
+    let (entries, _) = parse.cursor().directive()?;
+    let cursor = Cursor::from_token_stream(entries);
+    Some(
+        cursor
+            .ident()
+            .map_or(String::new(), |(ident, _)| ident.clone()),
+    )
+}
+
+/// Steps into the directive at the cursor, returning a buffer positioned
+/// right after its name.
+pub(crate) fn directive<'a>(parse: &ParseBuffer<'a>) -> Result<(String, ParseBuffer<'a>)> {
+    let entries = parse.step(|cursor| {
+        if let Some((entries, rest)) = cursor.directive() {
+            cursor.set(rest);
+            Ok(entries)
+        } else {
+            Err(cursor.error("expected preprocessing directive"))
+        }
+    })?;
+    let content = parse.nested(Cursor::from_token_stream(entries));
+    let name = match content.cursor().ident() {
+        Some((name, _)) => {
+            content.skip();
+            name.clone()
+        }
+        None => String::new(),
+    };
+    Ok((name, content))
+}
+
+impl<T: Parse> Parse for Conditional<T> {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let mut branches = vec![];
+        let (name, content) = directive(parse)?;
+        let mut kind = match ConditionalKind::from_name(&name) {
+            Some(kind) if kind.opens() => kind,
+            _ => return Err(parse.error("expected #if, #ifdef or #ifndef")),
+        };
+        let mut condition = content.cursor().token_stream();
+        loop {
+            let mut items = vec![];
+            loop {
+                match peek_name(parse).as_deref() {
+                    Some("elif" | "elifdef" | "elifndef" | "else" | "endif") => break,
+                    _ if parse.is_empty() => {
+                        return Err(parse.error(format!("unterminated #{}", kind.name())))
+                    }
+                    _ => items.push(parse.parse()?),
+                }
+            }
+            branches.push(ConditionalBranch {
+                kind,
+                condition,
+                items,
+            });
+            let (name, content) = directive(parse)?;
+            if name == "endif" {
+                break Ok(Self { branches });
+            }
+            if kind == ConditionalKind::Else {
+                return Err(parse.error(format!("#{name} after #else")));
+            }
+            kind = ConditionalKind::from_name(&name).unwrap();
+            condition = content.cursor().token_stream();
+        }
+    }
+}
+
+impl Parse for Define {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let (name, content) = directive(parse)?;
+        if name != "define" {
+            return Err(parse.error("expected #define"));
+        }
+        let joined = content.cursor().joined_to_next();
+        let name = content.call(Ident::parse_any)?;
+        Ok(Self {
+            name,
+            params: if content.peek::<tokens::Paren>() && joined {
+                Some(content.parse()?)
+            } else {
+                None
+            },
+            body: content.cursor().token_stream(),
+        })
+    }
+}
+
+impl Parse for DefineParams {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let content;
+        let paren = parenthesized!(content in parse)?;
+        let mut params = Punctuated::new();
+        let mut variadic_name = None;
+        let mut variadic = None;
+        if !content.is_empty() {
+            loop {
+                if content.peek::<token![...]>() {
+                    variadic = Some(content.parse()?);
+                    break;
+                }
+                let param = content.parse()?;
+                if content.peek::<token![...]>() {
+                    variadic_name = Some(param);
+                    variadic = Some(content.parse()?);
+                    break;
+                }
+                params.push(param);
+                if !content.peek::<token![,]>() {
+                    break;
+                }
+                content.parse::<token![,]>()?;
+            }
+        }
+        if !content.is_empty() {
+            return Err(content.error("expected `,` or `)` after macro parameter"));
+        }
+        Ok(Self {
+            paren,
+            params,
+            variadic_name,
+            variadic,
+        })
+    }
+}
+
+impl Parse for Include {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let (name, content) = directive(parse)?;
+        let next = match name.as_str() {
+            "include" => false,
+            "include_next" => true,
+            _ => return Err(parse.error("expected #include")),
+        };
+        let path = match content.cursor().literal() {
+            Some((Literal::Str(path), rest)) if rest.token_tree().is_none() => {
+                IncludePath::Quoted(path.clone())
+            }
+            Some((Literal::Header(path), rest)) if rest.token_tree().is_none() => {
+                IncludePath::Angled(path.clone())
+            }
+            _ if content.is_empty() => return Err(content.error("expected header name")),
+            _ => IncludePath::Macro(content.cursor().token_stream()),
+        };
+        Ok(Self { next, path })
+    }
+}
+
+impl Parse for Directive {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let (name, content) = directive(parse)?;
+        Ok(Self {
+            name: if name.is_empty() {
+                None
+            } else {
+                Some(Ident::new_unchecked(name))
+            },
+            tokens: content.cursor().token_stream(),
+        })
+    }
+}
+
+mod quote {
+    use super::*;
+    use crate::tokens::TokenTree;
+    use crate::{to_tokens, ToTokens};
+
+    fn name(name: &str) -> TokenTree {
+        TokenTree::Ident(name.to_string())
+    }
+
+    impl<T: ToTokens> ToTokens for Conditional<T> {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { branches } = self;
+            for branch in branches {
+                branch.to_tokens(tokens);
+            }
+            to_tokens::directive(&to_tokens::multiple(|tokens| {
+                tokens.extend_one(name("endif"));
+            }))
+            .to_tokens(tokens);
+        }
+    }
+
+    impl<T: ToTokens> ToTokens for ConditionalBranch<T> {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                kind,
+                condition,
+                items,
+            } = self;
+            to_tokens::directive(&to_tokens::multiple(|tokens| {
+                tokens.extend_one(name(kind.name()));
+                condition.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+            items.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Define {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                name: ident,
+                params,
+                body,
+            } = self;
+            to_tokens::directive(&to_tokens::multiple(|tokens| {
+                tokens.extend_one(name("define"));
+                ident.to_tokens(tokens);
+                if params.is_some() {
+                    tokens.set_joint();
+                }
+                params.to_tokens(tokens);
+                body.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for DefineParams {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                paren: _,
+                params,
+                variadic_name,
+                variadic,
+            } = self;
+            to_tokens::parenthesized(&to_tokens::multiple(|tokens| {
+                params.to_tokens(tokens);
+                if let Some(variadic) = variadic {
+                    if !params.is_empty() {
+                        <token![,]>::default().to_tokens(tokens);
+                    }
+                    variadic_name.to_tokens(tokens);
+                    variadic.to_tokens(tokens);
+                }
+            }))
+            .to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Include {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { next, path } = self;
+            to_tokens::directive(&to_tokens::multiple(|tokens| {
+                tokens.extend_one(name(if *next { "include_next" } else { "include" }));
+                path.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for IncludePath {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                IncludePath::Quoted(path) => {
+                    tokens.extend_one(TokenTree::Literal(Literal::Str(path.clone())))
+                }
+                IncludePath::Angled(path) => {
+                    tokens.extend_one(TokenTree::Literal(Literal::Header(path.clone())))
+                }
+                IncludePath::Macro(ts) => ts.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for Directive {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { name, tokens: ts } = self;
+            to_tokens::directive(&to_tokens::multiple(|tokens| {
+                name.to_tokens(tokens);
+                ts.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::error::Pos;
+use crate::tokens::{Delimeter, TokenCell, TokenTree};
+use crate::TokenStream;
+use std::path::PathBuf;
+
+/// Blank lines larger than this are replaced by a line marker, same as gcc.
+const MAX_BLANK_LINES: usize = 8;
+
+#[derive(Clone, Debug)]
+pub struct EmitOptions {
+    /// File name written into the line markers.
+    pub file: PathBuf,
+    /// Emit `# <line> "<file>"` line markers, like `gcc -E` without `-P`.
+    pub line_markers: bool,
+    /// Keep comments in the output, like `gcc -E -C`. Comments only exist in
+    /// streams created by [`TokenStream::from_str_with_comments`].
+    pub keep_comments: bool,
+}
+
+impl Default for EmitOptions {
+    fn default() -> Self {
+        Self {
+            file: PathBuf::from("<stdin>"),
+            line_markers: true,
+            keep_comments: false,
+        }
+    }
+}
+
+/// Turns a (preprocessed) token stream back into source text, keeping the
+/// tokens on the lines they were lexed from where possible. Tokens carrying a
+/// logical file (see [`crate::error::SourceFile`]) get line markers with the
+/// usual gcc flags whenever the file changes.
+pub fn emit(ts: &TokenStream, options: &EmitOptions) -> String {
+    let mut emitter = Emitter {
+        options,
+        out: String::new(),
+        line: 1,
+        last_col: None,
+        joint: false,
+        resync: false,
+        file: None,
+        depth: 0,
+    };
+    emitter.stream(ts);
+    if !emitter.out.is_empty() && !emitter.out.ends_with('\n') {
+        emitter.out.push('\n');
+    }
+    emitter.out
+}
+
+struct Emitter<'a> {
+    options: &'a EmitOptions,
+    out: String,
+    line: usize,
+    last_col: Option<usize>,
+    joint: bool,
+    /// A directive was just emitted. Tokens following it on the same source
+    /// line (after a `_Pragma`) are put on a new line behind a line marker.
+    resync: bool,
+    file: Option<PathBuf>,
+    depth: usize,
+}
+
+impl<'a> Emitter<'a> {
+    fn stream(&mut self, ts: &TokenStream) {
+        for cell in ts.cells() {
+            self.cell(cell)
+        }
+    }
+
+    fn cell(&mut self, cell: &TokenCell) {
+        self.cell_inner(cell);
+        self.joint = cell.joint();
+    }
+
+    fn cell_inner(&mut self, cell: &TokenCell) {
+        match &cell.tt {
+            TokenTree::Comment(_) if !self.options.keep_comments => {}
+            TokenTree::Group(delim, inner) => {
+                let (open, close) = match delim {
+                    Delimeter::Paren => ("(", ")"),
+                    Delimeter::Bracket => ("[", "]"),
+                    Delimeter::Brace => ("{", "}"),
+                };
+                self.token(cell.pos(), Some(cell.space()), open);
+                self.stream(inner);
+                self.token(cell.close(), None, close);
+            }
+            TokenTree::Directive(inner) => {
+                // `_Pragma` leaves its directive wherever the operator was
+                self.end_line();
+                self.token(cell.pos(), Some(cell.space()), "#");
+                self.stream(inner);
+                self.end_line();
+                self.resync = true;
+            }
+            tt => self.token(cell.pos(), Some(cell.space()), &tt.to_string()),
+        }
+    }
+
+    /// `space` is whether the token was preceded by whitespace in the source,
+    /// if known.
+    fn token(&mut self, pos: Option<Pos>, space: Option<bool>, spelling: &str) {
+        let Some(pos) = pos else {
+            return self.push(None, None, spelling);
+        };
+        let path = pos
+            .file
+            .as_ref()
+            .map_or(&self.options.file, |file| &file.path);
+        let resync = std::mem::take(&mut self.resync);
+        if self.file.as_ref() != Some(path) {
+            let depth = pos.file.as_ref().map_or(0, |file| file.include_depth);
+            self.enter(path.clone(), depth, pos.in_system_header(), pos.row);
+            self.indent(pos.col);
+        } else if resync && pos.row < self.line {
+            if self.options.line_markers {
+                self.line_marker(pos.row, &[]);
+            }
+            self.line = pos.row;
+            self.indent(pos.col);
+        } else if pos.row > self.line {
+            let gap = pos.row - self.line;
+            if gap > MAX_BLANK_LINES && self.options.line_markers {
+                self.end_line();
+                self.line_marker(pos.row, &[]);
+            } else {
+                (0..gap).for_each(|_| self.out.push('\n'));
+            }
+            self.line = pos.row;
+            self.indent(pos.col);
+        }
+        self.push(Some(pos.col), space, spelling);
+    }
+
+    fn enter(&mut self, path: PathBuf, depth: usize, system_header: bool, row: usize) {
+        // `self.depth` starts out as the main file's, so a stream that begins
+        // inside a header still marks entering it
+        let mut flags = vec![];
+        if depth > self.depth {
+            flags.push(1);
+        } else if depth < self.depth {
+            flags.push(2);
+        }
+        if system_header {
+            flags.push(3);
+        }
+        self.file = Some(path);
+        self.depth = depth;
+        self.end_line();
+        if self.options.line_markers {
+            self.line_marker(row, &flags);
+        }
+        self.line = row;
+    }
+
+    fn push(&mut self, col: Option<usize>, space: Option<bool>, spelling: &str) {
+        let adjacent = self.joint
+            || match space {
+                Some(space) => !space && col.is_some(),
+                None => matches!((self.last_col, col), (Some(last), Some(col)) if last == col),
+            };
+        self.joint = false;
+        if !self.at_line_start() && !adjacent {
+            self.out.push(' ');
+        }
+        self.out.push_str(spelling);
+        let lines = spelling.matches('\n').count();
+        self.line += lines;
+        self.last_col = match (lines, col) {
+            (0, Some(col)) => Some(col + spelling.chars().count()),
+            _ => None,
+        };
+    }
+
+    fn line_marker(&mut self, line: usize, flags: &[u8]) {
+        let file = self.file.as_ref().unwrap_or(&self.options.file);
+        let mut marker = format!("# {line} \"{}\"", file.display());
+        for flag in flags {
+            marker.push_str(&format!(" {flag}"));
+        }
+        self.out.push_str(&marker);
+        self.out.push('\n');
+        self.line = line;
+    }
+
+    fn end_line(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+            self.line += 1;
+        }
+    }
+
+    fn indent(&mut self, col: usize) {
+        self.last_col = None;
+        self.out.extend((1..col).map(|_| ' '));
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n') || self.out.ends_with(' ')
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{tokens, Expr, Ident, Punctuated};
+
+ast_struct! {
+    /// `enum color { RED, GREEN = 2, }`, or a reference to a tag, `enum color`.
+    pub struct EnumSpecifier {
+        pub enum_tk: token![enum],
+        pub tag: Option<Ident>,
+        pub enumerators: Option<EnumeratorList>,
+    }
+}
+
+ast_struct! {
+    pub struct EnumeratorList {
+        pub brace: tokens::Brace,
+        pub items: Punctuated<Enumerator, token![,]>,
+    }
+}
+
+ast_struct! {
+    pub struct Enumerator {
+        pub ident: Ident,
+        pub value: Option<(token![=], Box<Expr>)>,
+    }
+}
+
+impl EnumSpecifier {
+    /// A definition, as opposed to a reference to a tag.
+    pub fn is_definition(&self) -> bool {
+        self.enumerators.is_some()
+    }
+
+    /// The enumerators with their explicit values, empty for a reference to
+    /// a tag.
+    pub fn enumerators(&self) -> impl Iterator<Item = (&Ident, Option<&Expr>)> {
+        self.enumerators
+            .iter()
+            .flat_map(|list| list.items.iter())
+            .map(|enumerator| (&enumerator.ident, enumerator.value()))
+    }
+}
+
+impl Enumerator {
+    pub fn value(&self) -> Option<&Expr> {
+        self.value.as_ref().map(|(_, value)| value.as_ref())
+    }
+}
+
+use crate::expr::parsing::parse_assign;
+use crate::{Parse, ParseStream, Result};
+
+impl Parse for EnumSpecifier {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let enum_tk = parse.parse()?;
+        let tag: Option<Ident> = parse.parse()?;
+        let enumerators = if parse.peek::<tokens::Brace>() {
+            Some(parse.parse()?)
+        } else if tag.is_none() {
+            return Err(parse.error("expected tag or enumerator list"));
+        } else {
+            None
+        };
+        Ok(Self {
+            enum_tk,
+            tag,
+            enumerators,
+        })
+    }
+}
+
+impl Parse for EnumeratorList {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let content;
+        let brace = braced!(content in parse)?;
+        let items = content.call(Punctuated::parse_terminated)?;
+        if items.is_empty() {
+            return Err(content.error("expected enumerator"));
+        }
+        if !content.is_empty() {
+            return Err(content.error("expected `,` or `}` after enumerator"));
+        }
+        Ok(Self { brace, items })
+    }
+}
+
+impl Parse for Enumerator {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let ident = parse.parse()?;
+        let value = if parse.peek::<token![=]>() {
+            Some((parse.parse()?, Box::new(parse_assign(parse)?)))
+        } else {
+            None
+        };
+        Ok(Self { ident, value })
+    }
+}
+
+mod quote {
+    use super::*;
+    use crate::{to_tokens, ToTokens, TokenStream};
+
+    impl ToTokens for EnumSpecifier {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                enum_tk,
+                tag,
+                enumerators,
+            } = self;
+            enum_tk.to_tokens(tokens);
+            tag.to_tokens(tokens);
+            enumerators.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for EnumeratorList {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { brace: _, items } = self;
+            to_tokens::braced(items).to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Enumerator {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { ident, value } = self;
+            ident.to_tokens(tokens);
+            if let Some((eq, value)) = value {
+                eq.to_tokens(tokens);
+                value.to_tokens(tokens);
+            }
+        }
+    }
+}
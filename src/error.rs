@@ -3,17 +3,40 @@ use crate::tokens::{Delimeter, Punct, TokenTree};
 use crate::TokenStream;
 use std::fmt::{self, Display};
 use std::path::PathBuf;
+use std::rc::Rc;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct Pos {
     pub col: usize,
     pub row: usize,
+    /// The logical file as set by the last line marker, the row is relative
+    /// to it.
+    pub file: Option<Rc<SourceFile>>,
 }
 
 impl Pos {
     pub fn zero() -> Self {
-        Self { col: 0, row: 0 }
+        Self {
+            col: 0,
+            row: 0,
+            file: None,
+        }
     }
+
+    pub fn in_system_header(&self) -> bool {
+        self.file.as_ref().is_some_and(|file| file.system_header)
+    }
+}
+
+/// A file introduced by a `# 42 "foo.h" 1 3` or `#line 42 "foo.h"` marker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    /// Set by the `3` flag of a gcc line marker.
+    pub system_header: bool,
+    /// Number of files this one is nested in, as tracked by the `1` (enter)
+    /// and `2` (leave) flags of gcc line markers.
+    pub include_depth: usize,
 }
 
 #[derive(Debug)]
@@ -35,7 +58,20 @@ impl<'a> Error<'a> {
         Self {
             msg: msg.to_string(),
             file,
-            pos: Some(Pos { col, row }),
+            pos: Some(Pos {
+                col,
+                row,
+                file: None,
+            }),
+            cursor,
+        }
+    }
+
+    pub(crate) fn new_at(msg: impl Display, cursor: Option<Cursor<'a>>, pos: Pos) -> Self {
+        Self {
+            msg: msg.to_string(),
+            file: pos.file.as_ref().map(|file| file.path.clone()),
+            pos: Some(pos),
             cursor,
         }
     }
@@ -58,7 +94,7 @@ impl<'a> Error<'a> {
     }
 
     pub fn pos(&self) -> Option<Pos> {
-        self.pos
+        self.pos.clone()
     }
 }
 
@@ -72,7 +108,7 @@ impl<'a> Display for Error<'a> {
             pos,
             cursor,
         } = self;
-        let pos = if let Some(Pos { col, row }) = pos {
+        let pos = if let Some(Pos { col, row, .. }) = pos {
             let msg = format!("{row}:{col} ");
             if let Some(Some(file)) = file.as_ref().map(|file| file.to_str()) {
                 format!("{file} {msg}")
@@ -14,9 +14,11 @@ ast_enum! {
         Postfix(ExprPostfix),
         Initialize(ExprInitialize),
         Sizeof(ExprSizeof),
+        SizeofType(ExprSizeofType),
         Alignof(ExprAlignof),
         Ternary(ExprTernary),
         Comma(ExprComma),
+        Generic(ExprGeneric),
     }
 }
 
@@ -24,7 +26,7 @@ use crate::func::FnArgs;
 use crate::initializer::InitializerList;
 use crate::op::{AssignOp, PostOp};
 use crate::op::{BiOp, UnOp};
-use crate::{tokens, Ident, Lit, Ty};
+use crate::{tokens, Ident, Lit, Punctuated, TypeName};
 
 ast_struct! {
     pub struct ExprIdent {
@@ -48,7 +50,7 @@ ast_struct! {
 ast_struct! {
     pub struct ExprCast {
         pub paren: tokens::Paren,
-        pub ty: Ty,
+        pub ty: TypeName,
         pub expr: Box<Expr>,
     }
 }
@@ -117,12 +119,13 @@ ast_struct! {
 ast_struct! {
     pub struct ExprInitialize {
         pub paren: tokens::Paren,
-        pub ty: Ty,
+        pub ty: TypeName,
         pub init_list: InitializerList,
     }
 }
 
 ast_struct! {
+    /// `sizeof x`, also `sizeof (x)` and `sizeof (int){1}`.
     pub struct ExprSizeof {
         pub sizeof: token![sizeof],
         pub expr: Box<Expr>,
@@ -130,10 +133,27 @@ ast_struct! {
 }
 
 ast_struct! {
+    /// `sizeof(unsigned long)`
+    pub struct ExprSizeofType {
+        pub sizeof: token![sizeof],
+        pub paren: tokens::Paren,
+        pub ty: TypeName,
+    }
+}
+
+ast_struct! {
+    /// `_Alignof(double)`, spelled `alignof` since C23.
     pub struct ExprAlignof {
-        pub alignof: token![_Alignof],
+        pub alignof: AlignofKeyword,
         pub paren: tokens::Paren,
-        pub ty_name: Ident,
+        pub ty: TypeName,
+    }
+}
+
+ast_enum_simple! {
+    pub enum AlignofKeyword {
+        Alignof(token![_Alignof]),
+        C23(token![alignof]),
     }
 }
 
@@ -155,11 +175,58 @@ ast_struct! {
     }
 }
 
+ast_struct! {
+    /// `_Generic(x, float: f, default: g)`
+    pub struct ExprGeneric {
+        pub generic: token![_Generic],
+        pub paren: tokens::Paren,
+        pub expr: Box<Expr>,
+        pub comma: token![,],
+        pub associations: Punctuated<GenericAssociation, token![,]>,
+    }
+}
+
+ast_struct! {
+    pub struct GenericAssociation {
+        pub selector: GenericSelector,
+        pub colon: token![:],
+        pub expr: Box<Expr>,
+    }
+}
+
+ast_enum! {
+    pub enum GenericSelector {
+        Type(TypeName),
+        Default(token![default]),
+    }
+}
+
+impl ExprGeneric {
+    /// The expression selected when no type matches.
+    pub fn default_expr(&self) -> Option<&Expr> {
+        self.associations
+            .iter()
+            .find(|association| association.type_name().is_none())
+            .map(|association| association.expr.as_ref())
+    }
+}
+
+impl GenericAssociation {
+    /// The type to match, `None` for `default`.
+    pub fn type_name(&self) -> Option<&TypeName> {
+        match &self.selector {
+            GenericSelector::Type(ty) => Some(ty),
+            GenericSelector::Default(_) => None,
+        }
+    }
+}
+
 pub(crate) mod parsing {
     use super::*;
     use crate::lit::Lit;
     use crate::op::{AssignOp, BiOp, PostOp, UnOp};
     use crate::parse::ParseStream;
+    use crate::specifier::peek_specifier;
     use crate::{tokens, Ident, Parse, Result};
 
     impl Parse for Expr {
@@ -181,68 +248,134 @@ pub(crate) mod parsing {
             let content;
             let paren = parenthesized!(content in parse)?;
             let expr = Box::new(content.parse()?);
+            if !content.is_empty() {
+                return Err(content.error("expected `)`"));
+            }
             Expr::Paren(ExprParen { paren, expr })
+        } else if parse.peek::<token![_Generic]>() {
+            Expr::Generic(parse.parse()?)
         } else {
             return Err(parse.error("expected primary expression."));
         })
     }
 
+    impl Parse for ExprGeneric {
+        fn parse(parse: ParseStream) -> Result<Self> {
+            let generic = parse.parse()?;
+            let content;
+            let paren = parenthesized!(content in parse)?;
+            let expr_at = content.cursor();
+            let expr = Box::new(parse_assign(&content)?);
+            if content.is_empty() {
+                return Err(expr_at.error("expected `,` after controlling expression"));
+            }
+            let mut comma_at = content.cursor();
+            let comma = content.parse()?;
+            let mut associations = Punctuated::new();
+            let mut has_default = false;
+            loop {
+                if content.is_empty() {
+                    return Err(comma_at.error("expected generic association after `,`"));
+                }
+                let at = content.cursor();
+                let association: GenericAssociation = content.parse()?;
+                if association.type_name().is_none() {
+                    if has_default {
+                        return Err(at.error("duplicate `default` generic association"));
+                    }
+                    has_default = true;
+                }
+                associations.push(association);
+                if !content.peek::<token![,]>() {
+                    break;
+                }
+                comma_at = content.cursor();
+                content.parse::<token![,]>()?;
+            }
+            if !content.is_empty() {
+                return Err(content.error("expected `,` or `)` after generic association"));
+            }
+            Ok(Self {
+                generic,
+                paren,
+                expr,
+                comma,
+                associations,
+            })
+        }
+    }
+
+    impl Parse for GenericAssociation {
+        fn parse(parse: ParseStream) -> Result<Self> {
+            let selector = if parse.peek::<token![default]>() {
+                GenericSelector::Default(parse.parse()?)
+            } else {
+                GenericSelector::Type(parse.parse()?)
+            };
+            Ok(Self {
+                selector,
+                colon: parse.parse()?,
+                expr: Box::new(parse_assign(parse)?),
+            })
+        }
+    }
+
     fn parse_postfix(parse: ParseStream) -> Result<Expr> {
-        Ok(if false {
-            //let content;
-            //let _paren = parenthesized!(content in parse)?;
-            //let ty = content.parse()?;
-            //let content;
-            //let _brace = braced!(content in parse)?;
-            //let initializer_list = Punctuated::parse_terminated(&content)?;
-            //Expr::Construct(Construct {
-            //    ty,
-            //    initializer_list,
-            //})
-            unimplemented!()
+        let mut expr = if peek_paren_prefix(parse) == ParenPrefix::CompoundLiteral {
+            let content;
+            Expr::Initialize(ExprInitialize {
+                paren: parenthesized!(content in parse)?,
+                ty: parse_paren_type(&content)?,
+                init_list: parse.parse()?,
+            })
         } else {
-            let mut expr = parse_primary(parse)?;
-            'l: loop {
-                if parse.peek::<token![.]>() {
-                    expr = Expr::Member(ExprMember {
-                        body: Box::new(expr),
-                        dot: parse.parse()?,
-                        member: parse.parse()?,
-                    })
-                } else if parse.peek::<token![->]>() {
-                    expr = Expr::MemberPtr(ExprMemberPtr {
-                        body: Box::new(expr),
-                        arrow: parse.parse()?,
-                        member: parse.parse()?,
-                    })
-                } else if parse.peek::<token![++]>() {
-                    expr = Expr::Postfix(ExprPostfix {
-                        expr: Box::new(expr),
-                        op: PostOp::Inc(parse.parse()?),
-                    })
-                } else if parse.peek::<token![--]>() {
-                    expr = Expr::Postfix(ExprPostfix {
-                        expr: Box::new(expr),
-                        op: PostOp::Dec(parse.parse()?),
-                    })
-                } else if parse.peek::<tokens::Bracket>() {
-                    let content;
-                    expr = Expr::Subscript(ExprSubscript {
-                        body: Box::new(expr),
-                        bracket: bracketed!(content in &parse)?,
-                        index: Box::new(content.parse()?),
-                    })
-                } else if parse.peek::<tokens::Paren>() {
-                    expr = Expr::FnCall(ExprFnCall {
-                        body: Box::new(expr),
-                        args: parse.parse()?,
-                    })
-                } else {
-                    break 'l;
+            parse_primary(parse)?
+        };
+        'l: loop {
+            if parse.peek::<token![.]>() {
+                expr = Expr::Member(ExprMember {
+                    body: Box::new(expr),
+                    dot: parse.parse()?,
+                    member: parse.parse()?,
+                })
+            } else if parse.peek::<token![->]>() {
+                expr = Expr::MemberPtr(ExprMemberPtr {
+                    body: Box::new(expr),
+                    arrow: parse.parse()?,
+                    member: parse.parse()?,
+                })
+            } else if parse.peek::<token![++]>() {
+                expr = Expr::Postfix(ExprPostfix {
+                    expr: Box::new(expr),
+                    op: PostOp::Inc(parse.parse()?),
+                })
+            } else if parse.peek::<token![--]>() {
+                expr = Expr::Postfix(ExprPostfix {
+                    expr: Box::new(expr),
+                    op: PostOp::Dec(parse.parse()?),
+                })
+            } else if parse.peek::<tokens::Bracket>() {
+                let content;
+                let bracket = bracketed!(content in parse)?;
+                let index = Box::new(content.parse()?);
+                if !content.is_empty() {
+                    return Err(content.error("expected `]`"));
                 }
+                expr = Expr::Subscript(ExprSubscript {
+                    body: Box::new(expr),
+                    bracket,
+                    index,
+                })
+            } else if parse.peek::<tokens::Paren>() {
+                expr = Expr::FnCall(ExprFnCall {
+                    body: Box::new(expr),
+                    args: parse.parse()?,
+                })
+            } else {
+                break 'l;
             }
-            expr
-        })
+        }
+        Ok(expr)
     }
 
     fn parse_unary(parse: ParseStream) -> Result<Expr> {
@@ -264,188 +397,109 @@ pub(crate) mod parsing {
             } else if parse.peek::<token![!]>() {
                 UnOp::Not(parse.parse()?)
             } else if parse.peek::<token![sizeof]>() {
-                return Ok(Expr::Sizeof(ExprSizeof {
-                    sizeof: parse.parse()?,
-                    expr: parse.parse()?,
-                }));
-            } else if parse.peek::<token![_Alignof]>() {
+                let sizeof = parse.parse()?;
+                return Ok(if peek_paren_prefix(parse) == ParenPrefix::TypeName {
+                    let content;
+                    Expr::SizeofType(ExprSizeofType {
+                        sizeof,
+                        paren: parenthesized!(content in parse)?,
+                        ty: parse_paren_type(&content)?,
+                    })
+                } else {
+                    Expr::Sizeof(ExprSizeof {
+                        sizeof,
+                        expr: Box::new(parse_unary(parse)?),
+                    })
+                });
+            } else if parse.peek::<token![_Alignof]>() || parse.peek::<token![alignof]>() {
                 let content;
                 return Ok(Expr::Alignof(ExprAlignof {
                     alignof: parse.parse()?,
-                    paren: parenthesized!(content in &parse)?,
-                    ty_name: content.parse()?,
+                    paren: parenthesized!(content in parse)?,
+                    ty: parse_paren_type(&content)?,
                 }));
             } else {
-                return parse_postfix(&parse);
+                return parse_postfix(parse);
             };
             Expr::Unary(ExprUnary {
-                op: op,
+                op,
                 expr: Box::new(parse_cast(parse)?),
             })
         })
     }
 
-    fn parse_cast(parse: ParseStream) -> Result<Expr> {
-        if parse.peek::<tokens::Paren>() {
-            let content;
-            Ok(Expr::Cast(ExprCast {
-                paren: parenthesized!(content in parse)?,
-                ty: content.parse()?,
-                expr: Box::new(parse_cast(parse)?),
-            }))
-        } else {
-            parse_unary(parse)
-        }
-    }
-
-    fn parse_mul(parse: ParseStream) -> Result<Expr> {
-        let cast = parse_cast(parse)?;
-        Ok(Expr::Binary(ExprBinary {
-            op: if parse.peek::<token![*]>() {
-                BiOp::Mul(parse.parse()?)
-            } else if parse.peek::<token![/]>() {
-                BiOp::Div(parse.parse()?)
-            } else if parse.peek::<token![%]>() {
-                BiOp::Mod(parse.parse()?)
-            } else {
-                return Ok(cast);
-            },
-            lhs: Box::new(cast),
-            rhs: Box::new(parse_mul(parse)?),
-        }))
-    }
-
-    fn parse_add(parse: ParseStream) -> Result<Expr> {
-        let mul = parse_mul(parse)?;
-        Ok(Expr::Binary(ExprBinary {
-            op: if parse.peek::<token![+]>() {
-                BiOp::Add(parse.parse()?)
-            } else if parse.peek::<token![-]>() {
-                BiOp::Sub(parse.parse()?)
-            } else {
-                return Ok(mul);
-            },
-            lhs: Box::new(mul),
-            rhs: Box::new(parse_add(parse)?),
-        }))
-    }
-
-    fn parse_bwshift(parse: ParseStream) -> Result<Expr> {
-        let add = parse_add(parse)?;
-        Ok(Expr::Binary(ExprBinary {
-            op: if parse.peek::<token![<<]>() {
-                BiOp::LShft(parse.parse()?)
-            } else if parse.peek::<token![>>]>() {
-                BiOp::RShft(parse.parse()?)
-            } else {
-                return Ok(add);
-            },
-            lhs: Box::new(add),
-            rhs: Box::new(parse_bwshift(parse)?),
-        }))
-    }
-
-    fn parse_relational(parse: ParseStream) -> Result<Expr> {
-        let shift = parse_bwshift(parse)?;
-        Ok(Expr::Binary(ExprBinary {
-            op: if parse.peek::<token![<]>() {
-                BiOp::Less(parse.parse()?)
-            } else if parse.peek::<token![>]>() {
-                BiOp::Greater(parse.parse()?)
-            } else if parse.peek::<token![<=]>() {
-                BiOp::LessEq(parse.parse()?)
-            } else if parse.peek::<token![>=]>() {
-                BiOp::GreaterEq(parse.parse()?)
-            } else {
-                return Ok(shift);
-            },
-            lhs: Box::new(shift),
-            rhs: Box::new(parse_relational(parse)?),
-        }))
+    /// What a parenthesized prefix starts.
+    #[derive(PartialEq, Eq)]
+    enum ParenPrefix {
+        Expr,
+        /// A cast, or the operand of `sizeof`.
+        TypeName,
+        CompoundLiteral,
     }
 
-    fn parse_eq(parse: ParseStream) -> Result<Expr> {
-        let relational = parse_relational(parse)?;
-        Ok(Expr::Binary(ExprBinary {
-            op: if parse.peek::<token![==]>() {
-                BiOp::Eq(parse.parse()?)
-            } else if parse.peek::<token![!=]>() {
-                BiOp::NotEq(parse.parse()?)
+    /// Classifies the parentheses ahead by whether their contents start a
+    /// type name, which needs the typedef names in scope: `(T)*p` is a cast,
+    /// `(a)*b` a multiplication and `(T){1}` a compound literal.
+    fn peek_paren_prefix(parse: ParseStream) -> ParenPrefix {
+        let fork = parse.fork();
+        let prefix = || -> Result<ParenPrefix> {
+            let content;
+            parenthesized!(content in &fork)?;
+            Ok(if !peek_specifier(&content) {
+                ParenPrefix::Expr
+            } else if fork.peek::<tokens::Brace>() {
+                ParenPrefix::CompoundLiteral
             } else {
-                return Ok(relational);
-            },
-            lhs: Box::new(relational),
-            rhs: Box::new(parse_eq(parse)?),
-        }))
-    }
-
-    fn parse_bwand(parse: ParseStream) -> Result<Expr> {
-        let eq = parse_eq(parse)?;
-        Ok(if parse.peek::<token![&]>() {
-            Expr::Binary(ExprBinary {
-                lhs: Box::new(eq),
-                op: BiOp::BwAnd(parse.parse()?),
-                rhs: Box::new(parse_bwand(parse)?),
+                ParenPrefix::TypeName
             })
-        } else {
-            eq
-        })
+        };
+        prefix().unwrap_or(ParenPrefix::Expr)
     }
 
-    fn parse_bwxor(parse: ParseStream) -> Result<Expr> {
-        let and = parse_bwand(parse)?;
-        Ok(if parse.peek::<token![^]>() {
-            Expr::Binary(ExprBinary {
-                lhs: Box::new(and),
-                op: BiOp::BwOr(parse.parse()?),
-                rhs: Box::new(parse_bwxor(parse)?),
-            })
-        } else {
-            and
-        })
-    }
-
-    fn parse_bwor(parse: ParseStream) -> Result<Expr> {
-        let xor = parse_bwxor(parse)?;
-        Ok(if parse.peek::<token![|]>() {
-            Expr::Binary(ExprBinary {
-                lhs: Box::new(xor),
-                op: BiOp::BwOr(parse.parse()?),
-                rhs: Box::new(parse_bwor(parse)?),
-            })
-        } else {
-            xor
-        })
-    }
-
-    fn parse_land(parse: ParseStream) -> Result<Expr> {
-        let or = parse_bwor(parse)?;
-        Ok(if parse.peek::<token![&&]>() {
-            Expr::Binary(ExprBinary {
-                lhs: Box::new(or),
-                op: BiOp::And(parse.parse()?),
-                rhs: Box::new(parse_land(parse)?),
-            })
-        } else {
-            or
-        })
+    fn parse_cast(parse: ParseStream) -> Result<Expr> {
+        if peek_paren_prefix(parse) != ParenPrefix::TypeName {
+            return parse_unary(parse);
+        }
+        let content;
+        Ok(Expr::Cast(ExprCast {
+            paren: parenthesized!(content in parse)?,
+            ty: parse_paren_type(&content)?,
+            expr: Box::new(parse_cast(parse)?),
+        }))
     }
 
-    fn parse_lor(parse: ParseStream) -> Result<Expr> {
-        let and = parse_land(parse)?;
-        Ok(if parse.peek::<token![||]>() {
-            Expr::Binary(ExprBinary {
-                lhs: Box::new(and),
-                op: BiOp::Or(parse.parse()?),
-                rhs: Box::new(parse_lor(parse)?),
-            })
-        } else {
-            and
-        })
+    /// The type name filling the parentheses of a cast, `sizeof` or
+    /// compound literal.
+    fn parse_paren_type(content: ParseStream) -> Result<TypeName> {
+        let ty = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after type name"));
+        }
+        Ok(ty)
+    }
+
+    /// Binary operators binding at least as tight as `min`, see
+    /// [`BiOp::precedence`].
+    fn parse_binary(parse: ParseStream, min: u8) -> Result<Expr> {
+        let mut lhs = parse_cast(parse)?;
+        loop {
+            let fork = parse.fork();
+            let op = match fork.parse::<BiOp>() {
+                Ok(op) if op.precedence() >= min => op,
+                _ => break Ok(lhs),
+            };
+            parse.set(fork);
+            let rhs = parse_binary(parse, op.precedence() + 1)?;
+            lhs = Expr::Binary(ExprBinary {
+                lhs: Box::new(lhs),
+                op,
+                rhs: Box::new(rhs),
+            });
+        }
     }
 
     fn parse_cond(parse: ParseStream) -> Result<Expr> {
-        let cond_expr = parse_lor(parse)?;
+        let cond_expr = parse_binary(parse, 1)?;
         Ok(if parse.peek::<token![?]>() {
             Expr::Ternary(ExprTernary {
                 cond: Box::new(cond_expr),
@@ -460,53 +514,41 @@ pub(crate) mod parsing {
     }
 
     pub(crate) fn parse_assign(parse: ParseStream) -> Result<Expr> {
+        let lhs = parse_cond(parse)?;
         let fork = parse.fork();
-        if let Ok(unary) = parse_unary(&fork) {
-            let op = if fork.peek::<token![=]>() {
-                AssignOp::Eq(fork.parse()?)
-            } else if fork.peek::<token![*=]>() {
-                AssignOp::Mul(fork.parse()?)
-            } else if fork.peek::<token![/=]>() {
-                AssignOp::Div(fork.parse()?)
-            } else if fork.peek::<token![%=]>() {
-                AssignOp::Mod(fork.parse()?)
-            } else if fork.peek::<token![+=]>() {
-                AssignOp::Add(fork.parse()?)
-            } else if fork.peek::<token![-=]>() {
-                AssignOp::Sub(fork.parse()?)
-            } else if fork.peek::<token![<<=]>() {
-                AssignOp::LShft(fork.parse()?)
-            } else if fork.peek::<token![>>=]>() {
-                AssignOp::RShft(fork.parse()?)
-            } else if fork.peek::<token![&=]>() {
-                AssignOp::And(fork.parse()?)
-            } else if fork.peek::<token![^=]>() {
-                AssignOp::Xor(fork.parse()?)
-            } else if fork.peek::<token![|=]>() {
-                AssignOp::Or(fork.parse()?)
-            } else {
-                return parse_cond(&parse);
-            };
-            let lhs = Box::new(unary);
-            let rhs = Box::new(parse_assign(&fork)?);
-            parse.set(fork);
-            Ok(Expr::Assing(ExprAssign { lhs, op, rhs }))
-        } else {
-            parse_cond(parse)
+        let Ok(op) = fork.parse::<AssignOp>() else {
+            return Ok(lhs);
+        };
+        if !is_unary(&lhs) {
+            return Err(parse.error("expected unary expression before assignment operator"));
         }
+        parse.set(fork);
+        Ok(Expr::Assing(ExprAssign {
+            lhs: Box::new(lhs),
+            op,
+            rhs: Box::new(parse_assign(parse)?),
+        }))
+    }
+
+    /// Whether `expr` is a unary expression, the only kind that can be
+    /// assigned to.
+    fn is_unary(expr: &Expr) -> bool {
+        !matches!(
+            expr,
+            Expr::Cast(_) | Expr::Binary(_) | Expr::Ternary(_) | Expr::Assing(_) | Expr::Comma(_)
+        )
     }
 
     fn parse_comma(parse: ParseStream) -> Result<Expr> {
-        let expr = parse_assign(parse)?;
-        if parse.peek::<token![,]>() {
-            Ok(Expr::Comma(ExprComma {
+        let mut expr = parse_assign(parse)?;
+        while parse.peek::<token![,]>() {
+            expr = Expr::Comma(ExprComma {
                 left: Box::new(expr),
                 comma: parse.parse()?,
-                right: parse.parse()?,
-            }))
-        } else {
-            Ok(expr)
+                right: Box::new(parse_assign(parse)?),
+            });
         }
+        Ok(expr)
     }
 }
 
@@ -531,9 +573,11 @@ mod quote {
                 Expr::Postfix(e) => e.to_tokens(tokens),
                 Expr::Initialize(e) => e.to_tokens(tokens),
                 Expr::Sizeof(e) => e.to_tokens(tokens),
+                Expr::SizeofType(e) => e.to_tokens(tokens),
                 Expr::Alignof(e) => e.to_tokens(tokens),
                 Expr::Ternary(e) => e.to_tokens(tokens),
                 Expr::Comma(e) => e.to_tokens(tokens),
+                Expr::Generic(e) => e.to_tokens(tokens),
             }
         }
     }
@@ -663,15 +707,36 @@ mod quote {
         }
     }
 
+    impl ToTokens for ExprSizeofType {
+        fn to_tokens(&self, tokens: &mut crate::TokenStream) {
+            let Self {
+                sizeof,
+                paren: _,
+                ty,
+            } = self;
+            sizeof.to_tokens(tokens);
+            to_tokens::parenthesized(ty).to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for AlignofKeyword {
+        fn to_tokens(&self, tokens: &mut crate::TokenStream) {
+            match self {
+                AlignofKeyword::Alignof(t) => t.to_tokens(tokens),
+                AlignofKeyword::C23(t) => t.to_tokens(tokens),
+            }
+        }
+    }
+
     impl ToTokens for ExprAlignof {
         fn to_tokens(&self, tokens: &mut crate::TokenStream) {
             let Self {
                 alignof,
                 paren: _,
-                ty_name,
+                ty,
             } = self;
             alignof.to_tokens(tokens);
-            to_tokens::parenthesized(ty_name).to_tokens(tokens);
+            to_tokens::parenthesized(ty).to_tokens(tokens);
         }
     }
 
@@ -700,4 +765,45 @@ mod quote {
             right.to_tokens(tokens);
         }
     }
+
+    impl ToTokens for ExprGeneric {
+        fn to_tokens(&self, tokens: &mut crate::TokenStream) {
+            let Self {
+                generic,
+                paren: _,
+                expr,
+                comma,
+                associations,
+            } = self;
+            generic.to_tokens(tokens);
+            to_tokens::parenthesized(&to_tokens::multiple(|tokens| {
+                expr.to_tokens(tokens);
+                comma.to_tokens(tokens);
+                associations.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for GenericAssociation {
+        fn to_tokens(&self, tokens: &mut crate::TokenStream) {
+            let Self {
+                selector,
+                colon,
+                expr,
+            } = self;
+            selector.to_tokens(tokens);
+            colon.to_tokens(tokens);
+            expr.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for GenericSelector {
+        fn to_tokens(&self, tokens: &mut crate::TokenStream) {
+            match self {
+                GenericSelector::Type(e) => e.to_tokens(tokens),
+                GenericSelector::Default(e) => e.to_tokens(tokens),
+            }
+        }
+    }
 }
@@ -1,47 +1,38 @@
-use crate::tokens::Delimeter;
-use crate::{declr::DeclrList, tokens, Block, Ident, Punctuated, Ty};
+use crate::declr::{Declarator, DeclrList, Derivation};
+use crate::specifier::{DeclSpecifiers, Specifier, TypeSpecifier};
+use crate::{tokens, Block, Ident, Punctuated, TypeName};
 
 ast_struct! {
+    /// `static char *const *f(void) { ... }`, the declarator's first derivation is a function.
     pub struct Fn {
-        pub sign: FnSign,
-        pub params: FnParamsOrIdentList,
+        /// Missing for old-style definitions with an implicit `int`.
+        pub specifiers: Option<DeclSpecifiers>,
+        pub declarator: Box<Declarator>,
         pub declr_list: Option<DeclrList>,
         pub body: Block,
     }
 }
 
-ast_struct! {
-    pub struct FnSign {
-        pub ty: Option<Ty>,
-        pub ident: Ident,
-    }
-}
-
-ast_enum! {
-    pub enum FnParamsOrIdentList {
-        Params(FnParams),
-        Ident(FnIdentList),
-    }
-}
-
 ast_struct! {
     pub struct FnParams {
         pub paren: tokens::Paren,
         pub params: Punctuated<FnParam, token![,]>,
+        /// The `...` of `int printf(const char *fmt, ...)`.
+        pub variadic: Option<token![...]>,
     }
 }
 
 ast_enum! {
     pub enum FnParam {
         Named(FnParamNamed),
-        Unnamed(Ty),
+        Unnamed(TypeName),
     }
 }
 
 ast_struct! {
     pub struct FnParamNamed {
-        pub ty: Ty,
-        pub ident: Ident,
+        pub specifiers: DeclSpecifiers,
+        pub declarator: Declarator,
     }
 }
 
@@ -59,80 +50,179 @@ ast_struct! {
     }
 }
 
-use crate::{Declr, Expr, Parse, ParseStream, Result};
+impl FnParams {
+    /// `(void)`: the function takes no parameters.
+    pub fn is_void(&self) -> bool {
+        self.params.len() == 1 && self.params.iter().all(FnParam::is_void)
+    }
 
-impl Parse for Fn {
-    fn parse(parse: ParseStream) -> Result<Self> {
-        Ok(Self {
-            sign: parse.parse()?,
-            params: parse.parse()?,
-            declr_list: parse.parse()?,
-            body: parse.parse()?,
-        })
+    pub fn is_variadic(&self) -> bool {
+        self.variadic.is_some()
     }
 }
 
-impl Parse for FnSign {
-    fn parse(parse: ParseStream) -> Result<Self> {
-        Ok(Self {
-            ty: parse.parse()?,
-            ident: parse.parse()?,
-        })
+impl Fn {
+    pub fn ident(&self) -> &Ident {
+        self.declarator.ident()
+    }
+
+    /// The parameters, `None` for an old-style definition.
+    pub fn params(&self) -> Option<&FnParams> {
+        match self.declarator.derivations().first() {
+            Some(Derivation::Function(params)) => Some(params),
+            _ => None,
+        }
+    }
+
+    /// The identifier list of an old-style definition, whose parameters are
+    /// declared in [`Self::declr_list`].
+    pub fn ident_list(&self) -> Option<&FnIdentList> {
+        match self.declarator.derivations().first() {
+            Some(Derivation::IdentList(idents)) => Some(idents),
+            _ => None,
+        }
+    }
+
+    pub fn is_variadic(&self) -> bool {
+        self.params().is_some_and(FnParams::is_variadic)
+    }
+}
+
+impl FnParam {
+    /// An unnamed, unqualified `void`.
+    fn is_void(&self) -> bool {
+        match self {
+            FnParam::Unnamed(TypeName {
+                specifiers,
+                declarator: None,
+            }) => matches!(
+                specifiers.items[..],
+                [Specifier::Type(TypeSpecifier::Void(_))]
+            ),
+            _ => false,
+        }
     }
 }
 
-impl Parse for FnParamsOrIdentList {
+use crate::specifier::peek_specifier;
+use crate::{Expr, Parse, ParseStream, Result};
+
+impl Parse for Fn {
     fn parse(parse: ParseStream) -> Result<Self> {
-        let fork = parse.fork();
-        fork.step(|cursor| {
-            if let Some((_, next)) = cursor.group(Delimeter::Paren) {
-                cursor.set(next);
-                Ok(())
-            } else {
-                Err(cursor.error("expected parantheses"))
+        let specifiers = parse.parse()?;
+        let declarator: Box<Declarator> = parse.parse()?;
+        if !declarator.is_function() {
+            return Err(parse.error("expected function declarator"));
+        }
+        parse.scoped(|parse| {
+            declare_params(&declarator, parse);
+            let declr_list: Option<DeclrList> = parse.parse()?;
+            for declr in declr_list.iter().flat_map(|list| &list.items) {
+                declr.declare(parse);
             }
-        })?;
-        Ok(if fork.expect::<Declr>() {
-            Self::Ident(parse.parse()?)
-        } else {
-            Self::Params(parse.parse()?)
+            Ok(Self {
+                specifiers,
+                declarator,
+                declr_list,
+                body: parse.parse()?,
+            })
         })
     }
 }
 
+/// Parameter names hide typedef names in the function body.
+fn declare_params(declarator: &Declarator, parse: ParseStream) {
+    match declarator.derivations().first() {
+        Some(Derivation::Function(params)) => {
+            for param in params.params.iter() {
+                if let FnParam::Named(named) = param {
+                    parse.declare(named.declarator.ident().as_str(), false);
+                }
+            }
+        }
+        Some(Derivation::IdentList(list)) => {
+            for ident in list.idents.iter() {
+                parse.declare(ident.as_str(), false);
+            }
+        }
+        _ => {}
+    }
+}
+
 impl Parse for FnParams {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
+        let paren = parenthesized!(content in parse)?;
+        let mut params = Punctuated::new();
+        let mut variadic = None;
+        let mut void = None;
+        if !content.is_empty() {
+            loop {
+                if content.peek::<token![...]>() {
+                    variadic = Some(content.parse()?);
+                    if !content.is_empty() {
+                        return Err(content.error("expected `)` after `...`"));
+                    }
+                    break;
+                }
+                let cursor = content.cursor();
+                let param: FnParam = content.parse()?;
+                if param.is_void() {
+                    void.get_or_insert(cursor);
+                }
+                params.push(param);
+                if !content.peek::<token![,]>() {
+                    break;
+                }
+                content.parse::<token![,]>()?;
+            }
+        }
+        if !content.is_empty() {
+            return Err(content.error("expected `,` or `)` after parameter"));
+        }
+        if let Some(void) = void {
+            if params.len() > 1 || variadic.is_some() {
+                return Err(void.error("`void` must be the only parameter"));
+            }
+        }
         Ok(Self {
-            paren: parenthesized!(content in parse)?,
-            params: content.call(Punctuated::parse_non_terminated)?,
+            paren,
+            params,
+            variadic,
         })
     }
 }
 
 impl Parse for FnParam {
     fn parse(parse: ParseStream) -> Result<Self> {
-        Ok(if parse.fork().expect::<Ty>() {
-            let ty = parse.parse()?;
-            if parse.peek::<Ident>() {
-                Self::Named(FnParamNamed {
-                    ty,
-                    ident: parse.parse()?,
-                })
-            } else {
-                Self::Unnamed(ty)
-            }
-        } else {
+        if !peek_specifier(parse) {
             return Err(parse.error("expected type"));
-        })
+        }
+        let fork = parse.fork();
+        let err = match fork.parse() {
+            Ok(named) => {
+                parse.set(fork);
+                return Ok(Self::Named(named));
+            }
+            Err(err) => err,
+        };
+        // `int a[static]` reads as the type `int` followed by garbage, so
+        // the error of the named reading is the one to report then
+        let fork = parse.fork();
+        let ty = fork.parse()?;
+        if !fork.is_empty() && !fork.peek::<token![,]>() {
+            return Err(err);
+        }
+        parse.set(fork);
+        Ok(Self::Unnamed(ty))
     }
 }
 
 impl Parse for FnParamNamed {
     fn parse(parse: ParseStream) -> Result<Self> {
         Ok(Self {
-            ty: parse.parse()?,
-            ident: parse.parse()?,
+            specifiers: parse.parse()?,
+            declarator: parse.parse()?,
         })
     }
 }
@@ -150,10 +240,12 @@ impl Parse for FnIdentList {
 impl Parse for FnArgs {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
-        Ok(Self {
-            paren: parenthesized!(content in parse)?,
-            args: content.call(Punctuated::parse_non_terminated)?,
-        })
+        let paren = parenthesized!(content in parse)?;
+        let args = content.call(Punctuated::parse_non_terminated)?;
+        if !content.is_empty() {
+            return Err(content.error("expected `,` or `)` after argument"));
+        }
+        Ok(Self { paren, args })
     }
 }
 
@@ -164,39 +256,35 @@ mod quote {
     impl ToTokens for Fn {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let Self {
-                sign,
-                params,
+                specifiers,
+                declarator,
                 declr_list,
                 body,
             } = self;
-            sign.to_tokens(tokens);
-            params.to_tokens(tokens);
+            specifiers.to_tokens(tokens);
+            declarator.to_tokens(tokens);
             declr_list.to_tokens(tokens);
             body.to_tokens(tokens);
         }
     }
 
-    impl ToTokens for FnSign {
-        fn to_tokens(&self, tokens: &mut TokenStream) {
-            let Self { ty, ident } = self;
-            ty.to_tokens(tokens);
-            ident.to_tokens(tokens);
-        }
-    }
-
-    impl ToTokens for FnParamsOrIdentList {
-        fn to_tokens(&self, tokens: &mut TokenStream) {
-            match self {
-                FnParamsOrIdentList::Params(e) => e.to_tokens(tokens),
-                FnParamsOrIdentList::Ident(e) => e.to_tokens(tokens),
-            }
-        }
-    }
-
     impl ToTokens for FnParams {
         fn to_tokens(&self, tokens: &mut TokenStream) {
-            let Self { paren: _, params } = self;
-            to_tokens::parenthesized(params).to_tokens(tokens);
+            let Self {
+                paren: _,
+                params,
+                variadic,
+            } = self;
+            to_tokens::parenthesized(&to_tokens::multiple(|tokens| {
+                params.to_tokens(tokens);
+                if let Some(variadic) = variadic {
+                    if !params.is_empty() {
+                        <token![,]>::default().to_tokens(tokens);
+                    }
+                    variadic.to_tokens(tokens);
+                }
+            }))
+            .to_tokens(tokens);
         }
     }
 
@@ -211,9 +299,12 @@ mod quote {
 
     impl ToTokens for FnParamNamed {
         fn to_tokens(&self, tokens: &mut TokenStream) {
-            let Self { ty, ident } = self;
-            ty.to_tokens(tokens);
-            ident.to_tokens(tokens);
+            let Self {
+                specifiers,
+                declarator,
+            } = self;
+            specifiers.to_tokens(tokens);
+            declarator.to_tokens(tokens);
         }
     }
 
@@ -15,6 +15,14 @@ impl Ident {
         }
     }
 
+    pub(crate) fn new_unchecked(ident: String) -> Self {
+        Self(ident)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
     fn valid_ident(ident: &str) -> bool {
         !is_keyword(ident)
     }
@@ -50,6 +58,20 @@ impl Parse for Ident {
     }
 }
 
+impl Ident {
+    /// Parses any identifier, including keywords, as needed for macro names.
+    pub(crate) fn parse_any(parse: ParseStream) -> Result<Self> {
+        parse.step(|cursor| {
+            if let Some((ident, new)) = cursor.ident() {
+                cursor.set(new);
+                Ok(Self::new_unchecked(ident.clone()))
+            } else {
+                Err(parse.error("expected identifier"))
+            }
+        })
+    }
+}
+
 mod quote {
     use super::Ident;
     use crate::tokens::TokenTree;
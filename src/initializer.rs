@@ -1,38 +1,245 @@
+use crate::{tokens, Expr, Ident, Punctuated};
+
+ast_enum! {
+    /// The value of `int a = 1;` or `int a[] = {1, 2, 3};`.
+    pub enum Initializer {
+        Expr(Box<Expr>),
+        List(InitializerList),
+    }
+}
+
 ast_struct! {
-    pub struct InitializerList {}
+    /// `{ .x = 1, [2] = 3, 4, }`, which may be empty since C23.
+    pub struct InitializerList {
+        pub brace: tokens::Brace,
+        pub items: Punctuated<DesignatedInitializer, token![,]>,
+    }
 }
 
 ast_struct! {
-    pub struct Initializer { }
+    pub struct DesignatedInitializer {
+        pub designation: Option<Designation>,
+        pub initializer: Initializer,
+    }
 }
 
-use crate::{Parse, ParseStream, Result};
+ast_struct! {
+    /// `.a.b[2] =`
+    pub struct Designation {
+        pub designators: Vec<Designator>,
+        pub eq: token![=],
+    }
+}
+
+ast_enum! {
+    pub enum Designator {
+        Field(DesignatorField),
+        Index(DesignatorIndex),
+        Range(DesignatorRange),
+    }
+}
+
+ast_struct! {
+    pub struct DesignatorField {
+        pub dot: token![.],
+        pub ident: Ident,
+    }
+}
+
+ast_struct! {
+    pub struct DesignatorIndex {
+        pub bracket: tokens::Bracket,
+        pub index: Box<Expr>,
+    }
+}
+
+ast_struct! {
+    /// `[0 ... 9]`, a GNU extension.
+    pub struct DesignatorRange {
+        pub bracket: tokens::Bracket,
+        pub start: Box<Expr>,
+        pub dots: token![...],
+        pub end: Box<Expr>,
+    }
+}
+
+impl DesignatedInitializer {
+    /// The designators, empty for a positional initializer.
+    pub fn designators(&self) -> &[Designator] {
+        match &self.designation {
+            Some(designation) => &designation.designators,
+            None => &[],
+        }
+    }
+}
+
+use crate::expr::parsing::parse_assign;
+use crate::{Dialect, Parse, ParseStream, Result};
+
+impl Parse for Initializer {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        Ok(if parse.peek::<tokens::Brace>() {
+            Self::List(parse.parse()?)
+        } else {
+            Self::Expr(Box::new(parse_assign(parse)?))
+        })
+    }
+}
 
 impl Parse for InitializerList {
     fn parse(parse: ParseStream) -> Result<Self> {
-        todo!()
+        let content;
+        let brace = braced!(content in parse)?;
+        let items = content.call(Punctuated::parse_terminated)?;
+        if !content.is_empty() {
+            return Err(content.error("expected `,` or `}` after initializer"));
+        }
+        Ok(Self { brace, items })
     }
 }
 
-impl Parse for Initializer {
+impl Parse for DesignatedInitializer {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let designation = if parse.peek::<token![.]>() || parse.peek::<tokens::Bracket>() {
+            Some(parse.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            designation,
+            initializer: parse.parse()?,
+        })
+    }
+}
+
+impl Parse for Designation {
     fn parse(parse: ParseStream) -> Result<Self> {
-        todo!()
+        let start = parse.cursor();
+        let mut designators = vec![];
+        while parse.peek::<token![.]>() || parse.peek::<tokens::Bracket>() {
+            designators.push(parse.parse()?);
+        }
+        if !parse.peek::<token![=]>() {
+            return Err(start.error("expected `=` after designator"));
+        }
+        Ok(Self {
+            designators,
+            eq: parse.parse()?,
+        })
+    }
+}
+
+impl Parse for Designator {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        if parse.peek::<token![.]>() {
+            return Ok(Self::Field(DesignatorField {
+                dot: parse.parse()?,
+                ident: parse.parse()?,
+            }));
+        }
+        let content;
+        let bracket = bracketed!(content in parse)?;
+        let index = Box::new(parse_assign(&content)?);
+        let designator = if content.peek::<token![...]>() {
+            if parse.config().dialect != Dialect::Gnu {
+                return Err(content.error("range designators are a GNU extension"));
+            }
+            Self::Range(DesignatorRange {
+                bracket,
+                start: index,
+                dots: content.parse()?,
+                end: Box::new(parse_assign(&content)?),
+            })
+        } else {
+            Self::Index(DesignatorIndex { bracket, index })
+        };
+        if !content.is_empty() {
+            return Err(content.error("expected `]` after designator"));
+        }
+        Ok(designator)
     }
 }
 
 mod quote {
     use super::*;
-    use crate::{ToTokens, TokenStream};
+    use crate::{to_tokens, ToTokens, TokenStream};
+
+    impl ToTokens for Initializer {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                Initializer::Expr(e) => e.to_tokens(tokens),
+                Initializer::List(e) => e.to_tokens(tokens),
+            }
+        }
+    }
 
     impl ToTokens for InitializerList {
         fn to_tokens(&self, tokens: &mut TokenStream) {
-            todo!()
+            let Self { brace: _, items } = self;
+            to_tokens::braced(items).to_tokens(tokens);
         }
     }
 
-    impl ToTokens for Initializer {
+    impl ToTokens for DesignatedInitializer {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                designation,
+                initializer,
+            } = self;
+            designation.to_tokens(tokens);
+            initializer.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Designation {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { designators, eq } = self;
+            for designator in designators {
+                designator.to_tokens(tokens);
+            }
+            eq.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Designator {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                Designator::Field(e) => e.to_tokens(tokens),
+                Designator::Index(e) => e.to_tokens(tokens),
+                Designator::Range(e) => e.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for DesignatorField {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { dot, ident } = self;
+            dot.to_tokens(tokens);
+            ident.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for DesignatorIndex {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { bracket: _, index } = self;
+            to_tokens::bracketed(index).to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for DesignatorRange {
         fn to_tokens(&self, tokens: &mut TokenStream) {
-            todo!()
+            let Self {
+                bracket: _,
+                start,
+                dots,
+                end,
+            } = self;
+            to_tokens::bracketed(&to_tokens::multiple(|tokens| {
+                start.to_tokens(tokens);
+                dots.to_tokens(tokens);
+                end.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
         }
     }
 }
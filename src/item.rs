@@ -1,28 +1,65 @@
-use crate::{tokens, Declr, Fn, Parse, ParseStream, Result, Ty};
+use crate::declr::Declarator;
+use crate::directive::{self, Conditional, Define, Directive, DirectiveItem, Include};
+use crate::pragma::Pragma;
+use crate::specifier::DeclSpecifiers;
+use crate::static_assert::StaticAssert;
+use crate::{Declr, Fn, Parse, ParseStream, Result};
 
 pub enum Item {
     Declr(Declr),
     Fn(Fn),
+    StaticAssert(StaticAssert),
+    Conditional(Conditional<Item>),
+    Define(Define),
+    Include(Include),
+    Pragma(Pragma),
+    Directive(Directive),
 }
 
 impl Parse for Item {
     fn parse(parse: ParseStream) -> Result<Self> {
-        let fork = parse.fork();
-        fork.parse::<Option<Ty>>()?;
-        loop {
-            if fork.peek::<token![;]>() {
-                break Ok(Self::Declr(parse.parse()?));
-            } else if fork.peek::<tokens::Paren>() {
-                break Ok(Self::Fn(parse.parse()?));
-            } else if fork.is_empty() {
-                break Err(parse.error("expected item"));
-            } else {
-                fork.skip();
-            }
+        if let Some(directive) = directive::peek_item(parse)? {
+            return Ok(match directive {
+                DirectiveItem::Conditional => Self::Conditional(parse.parse()?),
+                DirectiveItem::Define => Self::Define(parse.parse()?),
+                DirectiveItem::Include => Self::Include(parse.parse()?),
+                DirectiveItem::Pragma => Self::Pragma(parse.parse()?),
+                DirectiveItem::Other => Self::Directive(parse.parse()?),
+            });
+        }
+        if parse.peek::<token![_Static_assert]>() {
+            Ok(Self::StaticAssert(parse.parse()?))
+        } else if peek_fn(parse) {
+            let f: Fn = parse.parse()?;
+            parse.declare(f.ident().as_str(), false);
+            Ok(Self::Fn(f))
+        } else {
+            let declr: Declr = parse.parse()?;
+            declr.declare(parse);
+            Ok(Self::Declr(declr))
         }
     }
 }
 
+/// Whether a function definition follows: declaration specifiers and a
+/// declarator whose first derivation is a function, not followed by the rest
+/// of a declaration.
+fn peek_fn(parse: ParseStream) -> bool {
+    let fork = parse.fork();
+    let is_fn = || -> Result<bool> {
+        fork.parse::<Option<DeclSpecifiers>>()?;
+        if fork.peek::<token![;]>() {
+            return Ok(false);
+        }
+        let declarator: Declarator = fork.parse()?;
+        Ok(declarator.is_function()
+            && !fork.peek::<token![;]>()
+            && !fork.peek::<token![,]>()
+            && !fork.peek::<token![=]>())
+    };
+    is_fn().unwrap_or(false)
+}
+
 mod quote {
     use super::Item;
     use crate::{ToTokens, TokenStream};
@@ -32,6 +69,12 @@ mod quote {
             match self {
                 Item::Declr(e) => e.to_tokens(tokens),
                 Item::Fn(e) => e.to_tokens(tokens),
+                Item::StaticAssert(e) => e.to_tokens(tokens),
+                Item::Conditional(e) => e.to_tokens(tokens),
+                Item::Define(e) => e.to_tokens(tokens),
+                Item::Include(e) => e.to_tokens(tokens),
+                Item::Pragma(e) => e.to_tokens(tokens),
+                Item::Directive(e) => e.to_tokens(tokens),
             }
         }
     }
@@ -25,7 +25,7 @@ ast_struct! {
 ast_struct! {
     pub struct Return {
         pub return_kw: token![return],
-        pub expr: Box<Expr>,
+        pub expr: Option<Box<Expr>>,
         pub semi_colon: token![;],
     }
 }
@@ -64,7 +64,11 @@ impl Parse for Return {
     fn parse(parse: ParseStream) -> Result<Self> {
         Ok(Self {
             return_kw: parse.parse()?,
-            expr: parse.parse()?,
+            expr: if parse.peek::<token![;]>() {
+                None
+            } else {
+                Some(parse.parse()?)
+            },
             semi_colon: parse.parse()?,
         })
     }
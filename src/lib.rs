@@ -12,6 +12,9 @@ pub mod peek;
 
 pub mod block;
 pub mod declr;
+pub mod directive;
+pub mod emit;
+pub mod enumeration;
 pub mod error;
 pub mod expr;
 pub mod file;
@@ -24,12 +27,18 @@ pub mod labeled;
 pub mod lit;
 pub mod loops;
 pub mod op;
+pub mod pp;
+pub mod pragma;
 pub mod selection;
 pub mod span;
 pub mod specifier;
+pub mod static_assert;
 pub mod stmnt;
+pub mod struct_union;
+pub mod target;
 pub mod to_tokens;
 pub mod ty;
+pub mod visit;
 
 mod buffers;
 
@@ -43,14 +52,14 @@ pub use ident::Ident;
 pub use item::Item;
 pub use jump::{Break, Continue, Goto, Return};
 pub use labeled::Label;
-pub use lit::{Lit, LitInt, LitStr};
+pub use lit::{Lit, LitChar, LitFloat, LitInt, LitStr};
 pub use loops::{DoWhile, For, While};
-pub use parse::{Parse, ParseStream, Punctuated};
+pub use parse::{Dialect, Parse, ParseConfig, ParseStream, Punctuated};
 pub use peek::Peek;
 pub use selection::{Case, Default, Else, If, Switch};
 pub use stmnt::Stmnt;
 pub use to_tokens::ToTokens;
-pub use ty::{Ptr, Ty};
+pub use ty::{Ptr, TypeName};
 
 pub type Result<T> = std::result::Result<T, Error<'static>>;
 
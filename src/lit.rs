@@ -2,12 +2,29 @@ ast_enum! {
     pub enum Lit{
         Int(LitInt),
         Str(LitStr),
+        Char(LitChar),
+        Float(LitFloat),
     }
 }
 
 ast_struct! {
     pub struct LitInt{
         value: i128,
+        unsigned: bool,
+        spelling: String,
+    }
+}
+
+ast_struct! {
+    pub struct LitFloat{
+        value: f64,
+        spelling: String,
+    }
+}
+
+ast_struct! {
+    pub struct LitChar{
+        char: String,
     }
 }
 
@@ -40,8 +57,19 @@ impl Parse for Lit {
                 cursor.set(rest);
                 Ok(match lit.clone() {
                     Literal::Str(str) => Self::Str(LitStr { str }),
-                    Literal::Int(value) => Self::Int(LitInt { value }),
-                    Literal::Float(_) => unimplemented!(),
+                    Literal::Int(value, spelling) => Self::Int(LitInt {
+                        value,
+                        unsigned: false,
+                        spelling,
+                    }),
+                    Literal::UInt(value, spelling) => Self::Int(LitInt {
+                        value,
+                        unsigned: true,
+                        spelling,
+                    }),
+                    Literal::Char(char) => Self::Char(LitChar { char }),
+                    Literal::Float(value, spelling) => Self::Float(LitFloat { value, spelling }),
+                    Literal::Header(_) => return Err(parse.error("unexpected header name")),
                 })
             } else {
                 Err(parse.error("expected literal"))
@@ -53,9 +81,21 @@ impl Parse for Lit {
 impl Parse for LitInt {
     fn parse(parse: ParseStream) -> Result<Self> {
         parse.step(|cursor| match cursor.literal() {
-            Some((Literal::Int(int), next)) => {
+            Some((Literal::Int(int, spelling), next)) => {
+                cursor.set(next);
+                Ok(Self {
+                    value: *int,
+                    unsigned: false,
+                    spelling: spelling.clone(),
+                })
+            }
+            Some((Literal::UInt(int, spelling), next)) => {
                 cursor.set(next);
-                Ok(Self { value: *int })
+                Ok(Self {
+                    value: *int,
+                    unsigned: true,
+                    spelling: spelling.clone(),
+                })
             }
             _ => Err(parse.error("expected integer literal")),
         })
@@ -69,13 +109,13 @@ impl Parse for LitStr {
                 cursor.set(next);
                 Ok(Self { str: str.clone() })
             }
-            _ => Err(parse.error("expected integer literal")),
+            _ => Err(parse.error("expected string literal")),
         })
     }
 }
 
 mod quote {
-    use super::{Lit, LitInt, LitStr};
+    use super::{Lit, LitChar, LitFloat, LitInt, LitStr};
     use crate::tokens::{Literal, TokenTree};
     use crate::{ToTokens, TokenStream};
 
@@ -84,13 +124,29 @@ mod quote {
             match self {
                 Lit::Int(int) => int.to_tokens(tokens),
                 Lit::Str(str) => str.to_tokens(tokens),
+                Lit::Char(char) => char.to_tokens(tokens),
+                Lit::Float(float) => float.to_tokens(tokens),
             }
         }
     }
 
     impl ToTokens for LitInt {
         fn to_tokens(&self, tokens: &mut TokenStream) {
-            tokens.extend_one(TokenTree::Literal(Literal::Int(self.value)));
+            let spelling = self.spelling.clone();
+            tokens.extend_one(TokenTree::Literal(if self.unsigned {
+                Literal::UInt(self.value, spelling)
+            } else {
+                Literal::Int(self.value, spelling)
+            }));
+        }
+    }
+
+    impl ToTokens for LitFloat {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.extend_one(TokenTree::Literal(Literal::Float(
+                self.value,
+                self.spelling.clone(),
+            )));
         }
     }
 
@@ -99,4 +155,10 @@ mod quote {
             tokens.extend_one(TokenTree::Literal(Literal::Str(self.str.clone())));
         }
     }
+
+    impl ToTokens for LitChar {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.extend_one(TokenTree::Literal(Literal::Char(self.char.clone())));
+        }
+    }
 }
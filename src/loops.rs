@@ -1,4 +1,4 @@
-use crate::{tokens, Expr, Stmnt};
+use crate::{tokens, Declr, Expr, Stmnt};
 
 ast_struct! {
     pub struct While {
@@ -24,24 +24,46 @@ ast_struct! {
     pub struct For {
         pub for_kw: token![for],
         pub paren: tokens::Paren,
-        pub initial: Option<Box<Expr>>,
-        pub colon0: token![;],
+        pub initial: ForInit,
         pub condition: Option<Box<Expr>>,
-        pub colon1: token![;],
+        pub semi_colon: token![;],
         pub post: Option<Box<Expr>>,
         pub stmnt: Box<Stmnt>,
     }
 }
 
+ast_enum! {
+    /// The first clause of a `for`, including its `;`.
+    pub enum ForInit {
+        Expr(ForInitExpr),
+        /// `int i = 0;`, scoped to the loop.
+        Declr(Declr),
+    }
+}
+
+ast_struct! {
+    pub struct ForInitExpr {
+        pub expr: Option<Box<Expr>>,
+        pub semi_colon: token![;],
+    }
+}
+
+use crate::specifier::peek_specifier;
 use crate::{Parse, ParseStream, Result};
 
 impl Parse for While {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
+        let while_kw = parse.parse()?;
+        let paren = parenthesized!(content in parse)?;
+        let condition = Box::new(content.parse()?);
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after while condition"));
+        }
         Ok(Self {
-            while_kw: parse.parse()?,
-            paren: parenthesized!(content in parse)?,
-            condition: Box::new(content.parse()?),
+            while_kw,
+            paren,
+            condition,
             stmnt: parse.parse()?,
         })
     }
@@ -50,12 +72,20 @@ impl Parse for While {
 impl Parse for DoWhile {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
+        let do_kw = parse.parse()?;
+        let stmnt = parse.parse()?;
+        let while_kw = parse.parse()?;
+        let paren = parenthesized!(content in parse)?;
+        let condition = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after while condition"));
+        }
         Ok(Self {
-            do_kw: parse.parse()?,
-            stmnt: parse.parse()?,
-            while_kw: parse.parse()?,
-            paren: parenthesized!(content in parse)?,
-            condition: content.parse()?,
+            do_kw,
+            stmnt,
+            while_kw,
+            paren,
+            condition,
             semi_colon: parse.parse()?,
         })
     }
@@ -63,22 +93,69 @@ impl Parse for DoWhile {
 
 impl Parse for For {
     fn parse(parse: ParseStream) -> Result<Self> {
-        let content;
-        Ok(Self {
-            for_kw: parse.parse()?,
-            paren: parenthesized!(content in parse)?,
-            initial: content.parse()?,
-            colon0: content.parse()?,
-            condition: content.parse()?,
-            colon1: content.parse()?,
-            post: content.parse()?,
-            stmnt: parse.parse()?,
+        parse.scoped(|parse| {
+            let content;
+            let for_kw = parse.parse()?;
+            let paren = parenthesized!(content in parse)?;
+            let initial_at = content.cursor();
+            let initial = content.parse()?;
+            // point at the condition, or at the clauses when it is missing
+            let condition_at = if content.is_empty() {
+                initial_at
+            } else {
+                content.cursor()
+            };
+            let condition = if content.peek::<token![;]>() || content.is_empty() {
+                None
+            } else {
+                Some(content.parse()?)
+            };
+            if content.is_empty() {
+                return Err(condition_at.error("expected `;` after for condition"));
+            }
+            let semi_colon = content.parse()?;
+            let post = if content.is_empty() {
+                None
+            } else {
+                Some(content.parse()?)
+            };
+            if !content.is_empty() {
+                return Err(content.error("expected `)` after for clauses"));
+            }
+            Ok(Self {
+                for_kw,
+                paren,
+                initial,
+                condition,
+                semi_colon,
+                post,
+                stmnt: parse.parse()?,
+            })
+        })
+    }
+}
+
+impl Parse for ForInit {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        Ok(if peek_specifier(parse) {
+            let declr: Declr = parse.parse()?;
+            declr.declare(parse);
+            Self::Declr(declr)
+        } else {
+            Self::Expr(ForInitExpr {
+                expr: if parse.peek::<token![;]>() {
+                    None
+                } else {
+                    Some(parse.parse()?)
+                },
+                semi_colon: parse.parse()?,
+            })
         })
     }
 }
 
 mod quote {
-    use super::{DoWhile, For, While};
+    use super::{DoWhile, For, ForInit, ForInitExpr, While};
     use crate::{to_tokens, ToTokens, TokenStream};
 
     impl ToTokens for While {
@@ -119,22 +196,37 @@ mod quote {
                 for_kw,
                 paren: _,
                 initial,
-                colon0,
                 condition,
-                colon1,
+                semi_colon,
                 post,
                 stmnt,
             } = self;
             for_kw.to_tokens(tokens);
             to_tokens::parenthesized(&to_tokens::multiple(|tokens| {
                 initial.to_tokens(tokens);
-                colon0.to_tokens(tokens);
                 condition.to_tokens(tokens);
-                colon1.to_tokens(tokens);
+                semi_colon.to_tokens(tokens);
                 post.to_tokens(tokens);
             }))
             .to_tokens(tokens);
             stmnt.to_tokens(tokens);
         }
     }
+
+    impl ToTokens for ForInit {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                ForInit::Expr(e) => e.to_tokens(tokens),
+                ForInit::Declr(e) => e.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for ForInitExpr {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { expr, semi_colon } = self;
+            expr.to_tokens(tokens);
+            semi_colon.to_tokens(tokens);
+        }
+    }
 }
@@ -34,6 +34,25 @@ ast_enum_simple! {
     }
 }
 
+impl BiOp {
+    /// Binding power, from 1 for `||` to 10 for `*`. All binary operators
+    /// are left associative.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BiOp::Mul(_) | BiOp::Div(_) | BiOp::Mod(_) => 10,
+            BiOp::Add(_) | BiOp::Sub(_) => 9,
+            BiOp::LShft(_) | BiOp::RShft(_) => 8,
+            BiOp::Less(_) | BiOp::Greater(_) | BiOp::LessEq(_) | BiOp::GreaterEq(_) => 7,
+            BiOp::Eq(_) | BiOp::NotEq(_) => 6,
+            BiOp::BwAnd(_) => 5,
+            BiOp::BwXor(_) => 4,
+            BiOp::BwOr(_) => 3,
+            BiOp::And(_) => 2,
+            BiOp::Or(_) => 1,
+        }
+    }
+}
+
 ast_enum_simple! {
     pub enum AssignOp {
         Eq(token![=]),
@@ -1,10 +1,36 @@
 use crate::buffers::ParseBuffer;
+use crate::pragma::PragmaRegistry;
 use crate::{Peek, Result};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
 pub type ParseStream<'a> = &'a ParseBuffer<'a>;
 
+#[derive(Clone, Debug, Default)]
+pub struct ParseConfig {
+    /// Keep preprocessing directives in the AST instead of rejecting them:
+    /// conditionals surrounding whole items or statements become
+    /// `Conditional` nodes, `#define` and `#include` get their own nodes and
+    /// everything else is kept as a raw `Directive`.
+    pub preserve_directives: bool,
+    /// Typed parsers for `#pragma`s, which are kept in any case.
+    pub pragmas: PragmaRegistry,
+    /// Names known to be typedefs at file scope, for parsing code whose
+    /// headers were not included, such as `size_t` or `FILE`.
+    pub typedef_names: Vec<String>,
+    /// Extensions such as the range designators of `{ [0 ... 9] = -1 }` are
+    /// only accepted for [`Dialect::Gnu`].
+    pub dialect: Dialect,
+}
+
+/// The C dialect to accept where GNU C differs from ISO C.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    #[default]
+    Iso,
+    Gnu,
+}
+
 pub trait Parse: Sized {
     fn parse(parse: ParseStream) -> Result<Self>;
 }
@@ -34,6 +60,34 @@ pub struct Punctuated<T, P> {
     terminated: bool,
 }
 
+impl<T, P> Punctuated<T, P> {
+    pub fn new() -> Self {
+        Self {
+            punctuated: vec![],
+            mark: Default::default(),
+            terminated: false,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.punctuated.push(value);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.punctuated.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.punctuated.is_empty()
+    }
+}
+
+impl<T, P> Default for Punctuated<T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Parse, P: Peek + Parse> Punctuated<T, P> {
     pub fn parse_non_terminated(parse: ParseStream) -> Result<Self> {
         let mut vec = vec![];
@@ -54,6 +108,27 @@ impl<T: Parse, P: Peek + Parse> Punctuated<T, P> {
         })
     }
 
+    /// Like [`Self::parse_non_terminated`], but accepts a trailing
+    /// punctuation, as in `{1, 2, 3,}`.
+    pub fn parse_terminated(parse: ParseStream) -> Result<Self> {
+        let mut vec = vec![];
+        let mut terminated = false;
+        while !parse.is_empty() {
+            vec.push(parse.parse()?);
+            terminated = parse.peek::<P>();
+            if terminated {
+                parse.parse::<P>()?;
+            } else {
+                break;
+            }
+        }
+        Ok(Self {
+            punctuated: vec,
+            mark: Default::default(),
+            terminated,
+        })
+    }
+
     pub fn len(&self) -> usize {
         self.punctuated.len()
     }
@@ -65,7 +140,7 @@ macro_rules! delim {
         $out = {
             let ts = $crate::tokens::$ty::parse_inner($parse)?;
             let cursor = $crate::buffers::Cursor::from_token_stream(&ts);
-            $crate::buffers::ParseBuffer::new(cursor)
+            $parse.nested(cursor)
         };
         Ok($crate::tokens::$ty)
     }};
@@ -0,0 +1,348 @@
+use super::{error, int, spelling, Preprocessor, Token};
+use crate::buffers::parse::ParsedTy;
+use crate::error::Pos;
+use crate::tokens::{Delimeter, Literal, Punct};
+use crate::Result;
+use std::collections::VecDeque;
+
+impl Preprocessor {
+    /// Evaluates the expression of an `#if` or `#elif`.
+    pub(super) fn eval(&mut self, tokens: Vec<Token>, pos: &Pos) -> Result<bool> {
+        let mut input = VecDeque::from(tokens);
+        let mut expanded = vec![];
+        while let Some(token) = input.pop_front() {
+            if token.ident() == Some("defined") {
+                let defined = self.is_defined(&defined_operand(&token, &mut input)?);
+                expanded.push(int(defined as i128, &token));
+            } else if !self.expand(&token, &mut input)? {
+                expanded.push(token);
+            }
+        }
+        if expanded.is_empty() {
+            return Err(error("#if with no expression", pos.clone()));
+        }
+        let mut eval = Eval {
+            tokens: &expanded,
+            i: 0,
+            pos,
+        };
+        let value = eval.conditional(true)?;
+        if let Some(token) = eval.peek() {
+            return Err(error(
+                format!(
+                    "missing binary operator before token \"{}\"",
+                    spelling(&token.parsed.ty)
+                ),
+                token.pos(),
+            ));
+        }
+        Ok(value.is_true())
+    }
+}
+
+/// A value of type `intmax_t` or `uintmax_t`, which is what `#if` evaluates
+/// in (C11 6.10.1p4).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Value {
+    Signed(i64),
+    Unsigned(u64),
+}
+
+impl Value {
+    /// Constants too large for `intmax_t` are unsigned, as in gcc.
+    fn literal(value: i128, unsigned: bool) -> Self {
+        if unsigned || value > i64::MAX as i128 {
+            Self::Unsigned(value as u64)
+        } else {
+            Self::Signed(value as i64)
+        }
+    }
+
+    fn bool(value: bool) -> Self {
+        Self::Signed(value as i64)
+    }
+
+    fn bits(self) -> u64 {
+        match self {
+            Self::Signed(value) => value as u64,
+            Self::Unsigned(value) => value,
+        }
+    }
+
+    fn is_true(self) -> bool {
+        self.bits() != 0
+    }
+
+    /// The usual arithmetic conversions: unsigned wins.
+    fn convert(lhs: Self, rhs: Self) -> (Self, Self) {
+        match (lhs, rhs) {
+            (Self::Signed(_), Self::Signed(_)) => (lhs, rhs),
+            _ => (Self::Unsigned(lhs.bits()), Self::Unsigned(rhs.bits())),
+        }
+    }
+
+    /// Applies `signed` or `unsigned` to operands of the same type.
+    fn map(
+        self,
+        rhs: Self,
+        signed: impl FnOnce(i64, i64) -> i64,
+        unsigned: impl FnOnce(u64, u64) -> u64,
+    ) -> Self {
+        match Self::convert(self, rhs) {
+            (Self::Signed(lhs), Self::Signed(rhs)) => Self::Signed(signed(lhs, rhs)),
+            (lhs, rhs) => Self::Unsigned(unsigned(lhs.bits(), rhs.bits())),
+        }
+    }
+
+    fn compare(self, rhs: Self) -> std::cmp::Ordering {
+        match Self::convert(self, rhs) {
+            (Self::Signed(lhs), Self::Signed(rhs)) => lhs.cmp(&rhs),
+            (lhs, rhs) => lhs.bits().cmp(&rhs.bits()),
+        }
+    }
+
+    /// Shifts keep the type of the left operand.
+    fn shift(self, rhs: Self, left: bool) -> Self {
+        let count = rhs.bits() as u32;
+        match (self, left) {
+            (Self::Signed(lhs), true) => Self::Signed(lhs.wrapping_shl(count)),
+            (Self::Signed(lhs), false) => Self::Signed(lhs.wrapping_shr(count)),
+            (Self::Unsigned(lhs), true) => Self::Unsigned(lhs.wrapping_shl(count)),
+            (Self::Unsigned(lhs), false) => Self::Unsigned(lhs.wrapping_shr(count)),
+        }
+    }
+}
+
+/// The macro name following `defined`, with or without parentheses.
+fn defined_operand(defined: &Token, input: &mut VecDeque<Token>) -> Result<String> {
+    let missing = |pos| error("operator \"defined\" requires an identifier", pos);
+    let paren = matches!(
+        input.front().map(|t| &t.parsed.ty),
+        Some(ParsedTy::Group(Delimeter::Paren))
+    );
+    if paren {
+        input.pop_front();
+    }
+    let name = match input.pop_front() {
+        Some(token) => match token.ident() {
+            Some(name) => name.to_string(),
+            None => return Err(missing(token.pos())),
+        },
+        None => return Err(missing(defined.pos())),
+    };
+    if paren {
+        match input.pop_front() {
+            Some(token) if matches!(token.parsed.ty, ParsedTy::End(Delimeter::Paren)) => {}
+            token => {
+                return Err(error(
+                    "missing ')' after \"defined\"",
+                    token.map_or(defined.pos(), |t| t.pos()),
+                ))
+            }
+        }
+    }
+    Ok(name)
+}
+
+/// The value of a character constant holding a single character or escape
+/// sequence.
+fn char_value(char: &str) -> Option<i64> {
+    let mut chars = char.chars();
+    let value = match chars.next()? {
+        '\\' => match chars.next()? {
+            'n' => '\n' as i64,
+            't' => '\t' as i64,
+            'r' => '\r' as i64,
+            'a' => 7,
+            'b' => 8,
+            'f' => 12,
+            'v' => 11,
+            c @ ('\\' | '\'' | '"' | '?') => c as i64,
+            'x' => return i64::from_str_radix(chars.as_str(), 16).ok(),
+            c @ '0'..='7' => {
+                let digits = format!("{c}{}", chars.as_str());
+                if digits.len() > 3 {
+                    return None;
+                }
+                return i64::from_str_radix(&digits, 8).ok();
+            }
+            _ => return None,
+        },
+        c => c as i64,
+    };
+    chars.next().is_none().then_some(value)
+}
+
+/// Evaluates fully expanded tokens. `eval` is false in operands that are
+/// skipped by `&&`, `||` or `?:`, where dividing by zero is fine.
+struct Eval<'a> {
+    tokens: &'a [Token],
+    i: usize,
+    /// Where the directive is, for errors at its end.
+    pos: &'a Pos,
+}
+
+fn binary(punct: &Punct) -> Option<u8> {
+    Some(match punct {
+        Punct::Asterix(_) | Punct::Slash(_) | Punct::Percent(_) => 10,
+        Punct::Plus(_) | Punct::Minus(_) => 9,
+        Punct::DoubleLeftArrow(_) | Punct::DoubleRightArrow(_) => 8,
+        Punct::LeftArrow(_)
+        | Punct::RightArrow(_)
+        | Punct::LeftEqualArrow(_)
+        | Punct::RightEqualArrow(_) => 7,
+        Punct::EqualEqual(_) | Punct::ExclamationEqual(_) => 6,
+        Punct::Ampersand(_) => 5,
+        Punct::Carat(_) => 4,
+        Punct::VerticalBar(_) => 3,
+        Punct::DoubleAmpersand(_) => 2,
+        Punct::DoubleVerticalBar(_) => 1,
+        _ => return None,
+    })
+}
+
+impl<'a> Eval<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.i)
+    }
+
+    fn peek_punct(&self) -> Option<Punct> {
+        match self.peek()?.parsed.ty {
+            ParsedTy::Punct(punct) => Some(punct),
+            _ => None,
+        }
+    }
+
+    fn missing(&self) -> crate::Error<'static> {
+        match self.peek() {
+            Some(token) => error(
+                format!(
+                    "token \"{}\" is not valid in preprocessor expressions",
+                    spelling(&token.parsed.ty)
+                ),
+                token.pos(),
+            ),
+            None => error("missing expression in #if", self.pos.clone()),
+        }
+    }
+
+    fn conditional(&mut self, eval: bool) -> Result<Value> {
+        let cond = self.binary(1, eval)?;
+        if !matches!(self.peek_punct(), Some(Punct::QuestionMark(_))) {
+            return Ok(cond);
+        }
+        self.i += 1;
+        let then = self.conditional(eval && cond.is_true())?;
+        if !matches!(self.peek_punct(), Some(Punct::Colon(_))) {
+            return Err(error("expected ':' in #if", self.end_pos()));
+        }
+        self.i += 1;
+        let otherwise = self.conditional(eval && !cond.is_true())?;
+        let (then, otherwise) = Value::convert(then, otherwise);
+        Ok(if cond.is_true() { then } else { otherwise })
+    }
+
+    /// Binary operators binding at least as tight as `min`.
+    fn binary(&mut self, min: u8, eval: bool) -> Result<Value> {
+        let mut lhs = self.unary(eval)?;
+        while let Some((punct, prec)) = self
+            .peek_punct()
+            .and_then(|punct| Some((punct, binary(&punct)?)))
+            .filter(|(_, prec)| *prec >= min)
+        {
+            let pos = self.peek().unwrap().pos();
+            self.i += 1;
+            let rhs_eval = match punct {
+                Punct::DoubleAmpersand(_) => eval && lhs.is_true(),
+                Punct::DoubleVerticalBar(_) => eval && !lhs.is_true(),
+                _ => eval,
+            };
+            let rhs = self.binary(prec + 1, rhs_eval)?;
+            lhs = match punct {
+                Punct::Asterix(_) => lhs.map(rhs, i64::wrapping_mul, u64::wrapping_mul),
+                Punct::Slash(_) | Punct::Percent(_) if !rhs.is_true() => {
+                    if eval {
+                        return Err(error("division by zero in #if", pos));
+                    }
+                    Value::convert(lhs, rhs).1
+                }
+                Punct::Slash(_) => lhs.map(rhs, i64::wrapping_div, u64::wrapping_div),
+                Punct::Percent(_) => lhs.map(rhs, i64::wrapping_rem, u64::wrapping_rem),
+                Punct::Plus(_) => lhs.map(rhs, i64::wrapping_add, u64::wrapping_add),
+                Punct::Minus(_) => lhs.map(rhs, i64::wrapping_sub, u64::wrapping_sub),
+                Punct::DoubleLeftArrow(_) => lhs.shift(rhs, true),
+                Punct::DoubleRightArrow(_) => lhs.shift(rhs, false),
+                Punct::LeftArrow(_) => Value::bool(lhs.compare(rhs).is_lt()),
+                Punct::RightArrow(_) => Value::bool(lhs.compare(rhs).is_gt()),
+                Punct::LeftEqualArrow(_) => Value::bool(lhs.compare(rhs).is_le()),
+                Punct::RightEqualArrow(_) => Value::bool(lhs.compare(rhs).is_ge()),
+                Punct::EqualEqual(_) => Value::bool(lhs.compare(rhs).is_eq()),
+                Punct::ExclamationEqual(_) => Value::bool(lhs.compare(rhs).is_ne()),
+                Punct::Ampersand(_) => lhs.map(rhs, |l, r| l & r, |l, r| l & r),
+                Punct::Carat(_) => lhs.map(rhs, |l, r| l ^ r, |l, r| l ^ r),
+                Punct::VerticalBar(_) => lhs.map(rhs, |l, r| l | r, |l, r| l | r),
+                Punct::DoubleAmpersand(_) => Value::bool(lhs.is_true() && rhs.is_true()),
+                Punct::DoubleVerticalBar(_) => Value::bool(lhs.is_true() || rhs.is_true()),
+                _ => unreachable!(),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self, eval: bool) -> Result<Value> {
+        let Some(token) = self.peek() else {
+            return Err(self.missing());
+        };
+        self.i += 1;
+        Ok(match &token.parsed.ty {
+            ParsedTy::Punct(Punct::Plus(_)) => self.unary(eval)?,
+            ParsedTy::Punct(Punct::Minus(_)) => match self.unary(eval)? {
+                Value::Signed(value) => Value::Signed(value.wrapping_neg()),
+                Value::Unsigned(value) => Value::Unsigned(value.wrapping_neg()),
+            },
+            ParsedTy::Punct(Punct::Tilde(_)) => match self.unary(eval)? {
+                Value::Signed(value) => Value::Signed(!value),
+                Value::Unsigned(value) => Value::Unsigned(!value),
+            },
+            ParsedTy::Punct(Punct::ExclamationMark(_)) => Value::bool(!self.unary(eval)?.is_true()),
+            ParsedTy::Group(Delimeter::Paren) => {
+                let value = self.conditional(eval)?;
+                match self.peek() {
+                    Some(token) if matches!(token.parsed.ty, ParsedTy::End(Delimeter::Paren)) => {
+                        self.i += 1;
+                    }
+                    _ => return Err(error("missing ')' in expression", token.pos())),
+                }
+                value
+            }
+            ParsedTy::Literal(Literal::Int(int, _)) => Value::literal(*int, false),
+            ParsedTy::Literal(Literal::UInt(int, _)) => Value::literal(*int, true),
+            ParsedTy::Literal(Literal::Char(char)) => match char_value(char) {
+                Some(value) => Value::Signed(value),
+                None => {
+                    return Err(error(
+                        format!("invalid character constant '{char}' in #if"),
+                        token.pos(),
+                    ))
+                }
+            },
+            ParsedTy::Literal(Literal::Float(..)) => {
+                return Err(error(
+                    "floating constant in preprocessor expression",
+                    token.pos(),
+                ))
+            }
+            // identifiers left after expansion
+            ParsedTy::Ident(_) => Value::Signed(0),
+            _ => {
+                self.i -= 1;
+                return Err(self.missing());
+            }
+        })
+    }
+
+    /// Position of the current token, or the directive if there is none.
+    fn end_pos(&self) -> Pos {
+        self.peek().map_or(self.pos.clone(), Token::pos)
+    }
+}
@@ -0,0 +1,601 @@
+use super::{error, spelling, take_line, Preprocessor, Token};
+use crate::buffers::parse::{self, Parsed, ParsedTy};
+use crate::error::Pos;
+use crate::tokens::{Delimeter, Literal, Punct};
+use crate::{Dialect, Result};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub(super) struct Macro {
+    /// `None` for object-like macros.
+    params: Option<Vec<String>>,
+    /// The name the variable arguments are referred to by if the macro takes
+    /// `...` after its named parameters: `__VA_ARGS__`, or `args` for the
+    /// GNU `args...` form.
+    variadic: Option<String>,
+    body: Vec<Parsed>,
+}
+
+/// An argument of a function-like macro invocation.
+struct Arg {
+    tokens: Vec<Token>,
+    /// The fully macro-expanded tokens, once needed.
+    expanded: Option<Vec<Token>>,
+}
+
+const VA_ARGS: &str = "__VA_ARGS__";
+const VA_OPT: &str = "__VA_OPT__";
+
+fn is_ident(parsed: Option<&Parsed>, name: &str) -> bool {
+    matches!(parsed, Some(Parsed { ty: ParsedTy::Ident(ident), .. }) if ident == name)
+}
+
+fn is_hash(parsed: Option<&Parsed>) -> bool {
+    matches!(
+        parsed,
+        Some(Parsed {
+            ty: ParsedTy::Punct(Punct::Hashtag(_)),
+            ..
+        })
+    )
+}
+
+fn is_paste(parsed: Option<&Parsed>) -> bool {
+    matches!(
+        parsed,
+        Some(Parsed {
+            ty: ParsedTy::Punct(Punct::DoubleHashtag(_)),
+            ..
+        })
+    )
+}
+
+fn is_comma(parsed: Option<&Parsed>) -> bool {
+    matches!(
+        parsed,
+        Some(Parsed {
+            ty: ParsedTy::Punct(Punct::Comma(_)),
+            ..
+        })
+    )
+}
+
+/// Index of the `)` closing the `(` at `open`.
+fn closing_paren(tokens: &[Parsed], open: usize) -> Option<usize> {
+    if !matches!(tokens.get(open)?.ty, ParsedTy::Group(Delimeter::Paren)) {
+        return None;
+    }
+    let mut depth = 0;
+    for (i, parsed) in tokens.iter().enumerate().skip(open) {
+        match parsed.ty {
+            ParsedTy::Group(Delimeter::Paren) => depth += 1,
+            ParsedTy::End(Delimeter::Paren) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+impl Macro {
+    /// Index of the argument `name` refers to.
+    fn param(&self, name: &str) -> Option<usize> {
+        let params = self.params.as_ref()?;
+        if self.variadic.as_deref() == Some(name) {
+            return Some(params.len());
+        }
+        params.iter().position(|param| param == name)
+    }
+
+    /// End of the parameter or `__VA_OPT__(...)` starting at `i`.
+    fn operand_end(&self, body: &[Parsed], i: usize) -> Option<usize> {
+        match &body.get(i)?.ty {
+            ParsedTy::Ident(ident) if self.variadic.is_some() && ident == VA_OPT => {
+                Some(closing_paren(body, i + 1).expect("checked in #define") + 1)
+            }
+            ParsedTy::Ident(ident) => self.param(ident).map(|_| i + 1),
+            _ => None,
+        }
+    }
+
+    /// Checks the replacement list, once the parameters are known.
+    fn check(&self, body: &[Parsed], in_va_opt: bool) -> Result<()> {
+        let edge = if in_va_opt {
+            "__VA_OPT__"
+        } else {
+            "a macro expansion"
+        };
+        for parsed in [body.first(), body.last()] {
+            if is_paste(parsed) {
+                return Err(error(
+                    format!("'##' cannot appear at either end of {edge}"),
+                    parsed.unwrap().pos(),
+                ));
+            }
+        }
+        let mut i = 0;
+        while i < body.len() {
+            let parsed = &body[i];
+            match &parsed.ty {
+                ParsedTy::Ident(ident) if ident == VA_ARGS && self.variadic.is_none() => {
+                    return Err(error(
+                        "__VA_ARGS__ can only appear in the expansion of a variadic macro",
+                        parsed.pos(),
+                    ))
+                }
+                ParsedTy::Ident(ident)
+                    if ident == VA_ARGS && self.variadic.as_deref() != Some(VA_ARGS) =>
+                {
+                    return Err(error(
+                        "__VA_ARGS__ can only appear in the expansion of a C99 variadic macro",
+                        parsed.pos(),
+                    ))
+                }
+                ParsedTy::Ident(ident) if ident == VA_OPT => {
+                    if self.variadic.is_none() {
+                        return Err(error(
+                            "__VA_OPT__ can only appear in the expansion of a variadic macro",
+                            parsed.pos(),
+                        ));
+                    }
+                    if in_va_opt {
+                        return Err(error(
+                            "__VA_OPT__ may not appear in a __VA_OPT__",
+                            parsed.pos(),
+                        ));
+                    }
+                    let Some(end) = closing_paren(body, i + 1) else {
+                        return Err(error("unterminated __VA_OPT__", parsed.pos()));
+                    };
+                    self.check(&body[i + 2..end], true)?;
+                    i = end;
+                }
+                ParsedTy::Punct(Punct::Hashtag(_))
+                    if self.params.is_some() && self.operand_end(body, i + 1).is_none() =>
+                {
+                    return Err(error(
+                        "'#' is not followed by a macro parameter",
+                        parsed.pos(),
+                    ))
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+}
+
+impl Preprocessor {
+    /// Handles `#define`, `rest` being the tokens after `define`.
+    pub(super) fn define_macro(&mut self, rest: Vec<Token>, pos: Pos) -> Result<()> {
+        let name = super::macro_name(&rest, "#define", pos.clone())?.to_string();
+        if name == "defined" {
+            return Err(error(
+                "\"defined\" cannot be used as a macro name",
+                rest[0].pos(),
+            ));
+        }
+        let mut body: Vec<_> = rest.into_iter().skip(1).map(|t| t.parsed).collect();
+        let (params, variadic) = match body.first() {
+            // only a parenthesis right after the name makes it function-like
+            Some(Parsed {
+                ty: ParsedTy::Group(Delimeter::Paren),
+                space: false,
+                ..
+            }) => {
+                let (params, variadic, end) = parse_params(&body)?;
+                body.drain(..end);
+                (Some(params), variadic)
+            }
+            _ => (None, None),
+        };
+        let define = Macro {
+            params,
+            variadic,
+            body,
+        };
+        define.check(&define.body, false)?;
+        self.macros.insert(name, Rc::new(define));
+        Ok(())
+    }
+
+    /// Expands `token` if it names a macro, pushing the replacement to the
+    /// front of `input` to be rescanned. The arguments of function-like
+    /// macros are taken from `input` too.
+    pub(super) fn expand(&mut self, token: &Token, input: &mut VecDeque<Token>) -> Result<bool> {
+        let Some(name) = token.ident() else {
+            return Ok(false);
+        };
+        if token.hide.iter().any(|hidden| hidden == name) {
+            return Ok(false);
+        }
+        let Some(define) = self.macros.get(name).cloned() else {
+            return Ok(false);
+        };
+        let (mut args, mut hide) = if define.params.is_some() {
+            match input.front() {
+                Some(next) if matches!(next.parsed.ty, ParsedTy::Group(Delimeter::Paren)) => {}
+                _ => return Ok(false),
+            }
+            input.pop_front();
+            let (args, rparen) = self.collect_args(token, &define, input)?;
+            let hide: Vec<_> = token
+                .hide
+                .iter()
+                .filter(|hidden| rparen.hide.contains(hidden))
+                .cloned()
+                .collect();
+            (args, hide)
+        } else {
+            (vec![], token.hide.clone())
+        };
+        hide.push(name.to_string());
+        let replacement = self.substitute(&define, &define.body, &mut args, token)?;
+        let mut replacement: Vec<_> = replacement.into_iter().flatten().collect();
+        if let Some(first) = replacement.first_mut() {
+            first.parsed.bol = token.parsed.bol;
+            first.parsed.space = token.parsed.space;
+        }
+        for mut replaced in replacement.into_iter().rev() {
+            for hidden in &hide {
+                if !replaced.hide.contains(hidden) {
+                    replaced.hide.push(hidden.clone());
+                }
+            }
+            input.push_front(replaced);
+        }
+        Ok(true)
+    }
+
+    /// Fully macro-expands `tokens` on their own, as done for arguments.
+    fn expand_all(&mut self, tokens: Vec<Token>) -> Result<Vec<Token>> {
+        let mut input = VecDeque::from(tokens);
+        let mut out = vec![];
+        while let Some(token) = input.pop_front() {
+            if !self.expand(&token, &mut input)? {
+                out.push(token);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Collects the arguments of the invocation of `name` up to the closing
+    /// parenthesis, which is returned too.
+    fn collect_args(
+        &mut self,
+        name: &Token,
+        define: &Macro,
+        input: &mut VecDeque<Token>,
+    ) -> Result<(Vec<Arg>, Token)> {
+        let macro_name = name.ident().unwrap();
+        let named = define.params.as_ref().map_or(0, Vec::len);
+        let mut args = vec![vec![]];
+        let mut commas = vec![];
+        let mut depth = 0;
+        let rparen = loop {
+            let Some(token) = input.pop_front() else {
+                return Err(error(
+                    format!("unterminated argument list invoking macro \"{macro_name}\""),
+                    name.pos(),
+                ));
+            };
+            if token.starts_directive() {
+                let line = take_line(input);
+                if let Some(kept) = self.directive(token, line)? {
+                    return Err(error(
+                        "preprocessing directive not allowed in macro arguments",
+                        kept[0].pos(),
+                    ));
+                }
+                continue;
+            }
+            if self.skipping() {
+                continue;
+            }
+            match token.parsed.ty {
+                ParsedTy::Group(Delimeter::Paren) => depth += 1,
+                ParsedTy::End(Delimeter::Paren) if depth == 0 => break token,
+                ParsedTy::End(Delimeter::Paren) => depth -= 1,
+                // the variable arguments take up all remaining commas
+                ParsedTy::Punct(Punct::Comma(_))
+                    if depth == 0 && !(define.variadic.is_some() && args.len() > named) =>
+                {
+                    commas.push(token.pos());
+                    args.push(vec![]);
+                    continue;
+                }
+                _ => {}
+            }
+            args.last_mut().unwrap().push(token);
+        };
+        let given = if args.len() == 1 && args[0].is_empty() && named == 0 {
+            0
+        } else {
+            args.len()
+        };
+        if given < named {
+            return Err(error(
+                format!(
+                    "macro \"{macro_name}\" requires {named} arguments, but only {given} given"
+                ),
+                rparen.pos(),
+            ));
+        }
+        if given > named && define.variadic.is_none() {
+            let pos = match named {
+                0 => args
+                    .iter()
+                    .flatten()
+                    .next()
+                    .map_or(rparen.pos(), Token::pos),
+                _ => commas[named - 1].clone(),
+            };
+            return Err(error(
+                format!("macro \"{macro_name}\" passed {given} arguments, but takes just {named}"),
+                pos,
+            ));
+        }
+        if define.variadic.is_some() && args.len() == named {
+            // the variable arguments were left out entirely
+            args.push(vec![]);
+        }
+        let args = args
+            .into_iter()
+            .map(|tokens| Arg {
+                tokens,
+                expanded: None,
+            })
+            .collect();
+        Ok((args, rparen))
+    }
+
+    fn expanded(&mut self, arg: &mut Arg) -> Result<Vec<Token>> {
+        if arg.expanded.is_none() {
+            arg.expanded = Some(self.expand_all(arg.tokens.clone())?);
+        }
+        Ok(arg.expanded.clone().unwrap())
+    }
+
+    /// The tokens the parameter or `__VA_OPT__` at `i` is replaced by,
+    /// `expand` telling whether arguments are macro-expanded first.
+    fn operand(
+        &mut self,
+        define: &Macro,
+        body: &[Parsed],
+        i: usize,
+        args: &mut [Arg],
+        at: &Token,
+        expand: bool,
+    ) -> Result<Vec<Token>> {
+        let ParsedTy::Ident(ident) = &body[i].ty else {
+            unreachable!()
+        };
+        if let Some(param) = define.param(ident) {
+            return if expand {
+                self.expanded(&mut args[param])
+            } else {
+                Ok(args[param].tokens.clone())
+            };
+        }
+        // `__VA_OPT__(...)`, left out unless the variable arguments expand
+        // to something
+        let va_args = define.param(VA_ARGS).unwrap();
+        if self.expanded(&mut args[va_args])?.is_empty() {
+            return Ok(vec![]);
+        }
+        let end = define.operand_end(body, i).unwrap() - 1;
+        let tokens = self.substitute(define, &body[i + 2..end], args, at)?;
+        Ok(tokens.into_iter().flatten().collect())
+    }
+
+    /// Replaces the parameters in `body`, applying `#` and `##`. Placemarkers,
+    /// standing in for empty arguments next to `##`, are `None`.
+    fn substitute(
+        &mut self,
+        define: &Macro,
+        body: &[Parsed],
+        args: &mut [Arg],
+        at: &Token,
+    ) -> Result<Vec<Option<Token>>> {
+        let mut out: Vec<Option<Token>> = vec![];
+        let mut i = 0;
+        while i < body.len() {
+            let parsed = &body[i];
+            if define.params.is_some() && is_hash(Some(parsed)) {
+                let end = define.operand_end(body, i + 1).unwrap();
+                let operand = self.operand(define, body, i + 1, args, at, false)?;
+                out.push(Some(stringify(&operand, at, parsed.space)));
+                i = end;
+                continue;
+            }
+            if let Some(va_args) = define.variadic.as_deref().filter(|va_args| {
+                self.dialect == Dialect::Gnu
+                    && is_comma(Some(parsed))
+                    && is_paste(body.get(i + 1))
+                    && is_ident(body.get(i + 2), va_args)
+            }) {
+                // `, ## __VA_ARGS__` drops the comma if there are no variable
+                // arguments
+                let va_args = &args[define.param(va_args).unwrap()].tokens;
+                if !va_args.is_empty() {
+                    out.push(Some(body_token(parsed, at)));
+                    out.extend(va_args.iter().cloned().map(Some));
+                }
+                i += 3;
+                continue;
+            }
+            if is_paste(Some(parsed)) {
+                let (rhs, end) = match define.operand_end(body, i + 1) {
+                    Some(end) => (self.operand(define, body, i + 1, args, at, false)?, end),
+                    None => (vec![body_token(&body[i + 1], at)], i + 2),
+                };
+                let mut rhs = rhs.into_iter();
+                if let Some(first) = rhs.next() {
+                    match out.pop() {
+                        Some(Some(lhs)) => out.push(Some(paste(lhs, first, parsed.pos())?)),
+                        _ => out.push(Some(first)),
+                    }
+                }
+                out.extend(rhs.map(Some));
+                i = end;
+                continue;
+            }
+            if let Some(end) = define.operand_end(body, i) {
+                let pasted = is_paste(body.get(end));
+                let mut tokens = self.operand(define, body, i, args, at, !pasted)?;
+                if tokens.is_empty() && pasted {
+                    out.push(None);
+                }
+                if let Some(first) = tokens.first_mut() {
+                    first.parsed.space = parsed.space;
+                }
+                out.extend(tokens.into_iter().map(Some));
+                i = end;
+                continue;
+            }
+            out.push(Some(body_token(parsed, at)));
+            i += 1;
+        }
+        Ok(out)
+    }
+}
+
+/// Parses the parameter list at the start of `tokens`, returning the named
+/// parameters, the name of the variable arguments if there's a `...` and the
+/// index after the `)`.
+fn parse_params(tokens: &[Parsed]) -> Result<(Vec<String>, Option<String>, usize)> {
+    let mut params = vec![];
+    let mut iter = tokens.iter().enumerate().skip(1);
+    let missing = || error("missing ')' in macro parameter list", tokens[0].pos());
+    if let Some((
+        i,
+        Parsed {
+            ty: ParsedTy::End(Delimeter::Paren),
+            ..
+        },
+    )) = iter.clone().next()
+    {
+        return Ok((params, None, i + 1));
+    }
+    loop {
+        let Some((_, param)) = iter.next() else {
+            return Err(missing());
+        };
+        let variadic = match &param.ty {
+            ParsedTy::Ident(ident) if ident == VA_ARGS => {
+                return Err(error(
+                    "__VA_ARGS__ can not be used as a parameter name",
+                    param.pos(),
+                ))
+            }
+            ParsedTy::Ident(ident) if params.contains(ident) => {
+                return Err(error(
+                    format!("duplicate macro parameter \"{ident}\""),
+                    param.pos(),
+                ))
+            }
+            // GNU `args...`
+            ParsedTy::Ident(ident)
+                if matches!(
+                    iter.clone().next(),
+                    Some((
+                        _,
+                        Parsed {
+                            ty: ParsedTy::Punct(Punct::DotDotDot(_)),
+                            ..
+                        }
+                    ))
+                ) =>
+            {
+                iter.next();
+                Some(ident.clone())
+            }
+            ParsedTy::Ident(ident) => {
+                params.push(ident.clone());
+                None
+            }
+            ParsedTy::Punct(Punct::DotDotDot(_)) => Some(VA_ARGS.to_string()),
+            _ => return Err(error("expected parameter name", param.pos())),
+        };
+        match iter.next() {
+            Some((
+                i,
+                Parsed {
+                    ty: ParsedTy::End(Delimeter::Paren),
+                    ..
+                },
+            )) => return Ok((params, variadic, i + 1)),
+            Some((
+                _,
+                Parsed {
+                    ty: ParsedTy::Punct(Punct::Comma(_)),
+                    ..
+                },
+            )) if variadic.is_none() => {}
+            Some((_, parsed)) if variadic.is_some() => {
+                return Err(error("expected ')' after \"...\"", parsed.pos()))
+            }
+            Some((_, parsed)) => {
+                return Err(error(
+                    "expected ',' or ')' in macro parameter list",
+                    parsed.pos(),
+                ))
+            }
+            None => return Err(missing()),
+        }
+    }
+}
+
+/// A token of the replacement list, placed at the invocation.
+fn body_token(parsed: &Parsed, at: &Token) -> Token {
+    Token::new(Parsed {
+        bol: false,
+        space: parsed.space,
+        ty: parsed.ty.clone(),
+        ..at.parsed.clone()
+    })
+}
+
+fn stringify(tokens: &[Token], at: &Token, space: bool) -> Token {
+    let mut str = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && token.parsed.space {
+            str.push(' ');
+        }
+        let spelling = spelling(&token.parsed.ty);
+        match token.parsed.ty {
+            ParsedTy::Literal(Literal::Str(_) | Literal::Char(_)) => {
+                str.push_str(&spelling.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            _ => str.push_str(&spelling),
+        }
+    }
+    Token::new(Parsed {
+        bol: false,
+        space,
+        ty: ParsedTy::Literal(Literal::Str(str)),
+        ..at.parsed.clone()
+    })
+}
+
+fn paste(lhs: Token, rhs: Token, pos: Pos) -> Result<Token> {
+    let (lhs_spelling, rhs_spelling) = (spelling(&lhs.parsed.ty), spelling(&rhs.parsed.ty));
+    match parse::lex_one(&format!("{lhs_spelling}{rhs_spelling}")) {
+        Some(ty) => Ok(Token {
+            parsed: Parsed { ty, ..lhs.parsed },
+            hide: lhs.hide,
+        }),
+        None => Err(error(
+            format!(
+                "pasting \"{lhs_spelling}\" and \"{rhs_spelling}\" does not give a valid preprocessing token"
+            ),
+            pos,
+        )),
+    }
+}
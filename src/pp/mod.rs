@@ -0,0 +1,319 @@
+//! A C preprocessor working on the flat tokens produced by the lexer, before
+//! groups are formed. Directives it doesn't handle itself (`#include`,
+//! `#pragma`, ...) are kept in the output.
+
+mod eval;
+mod expand;
+
+use crate::buffers::parse::{self, Parsed, ParsedTy};
+use crate::error::Pos;
+use crate::target::TargetProfile;
+use crate::tokens::{Delimeter, Hashtag, Literal, Punct, TokenTree};
+use crate::{Dialect, Error, Result, TokenStream};
+use expand::Macro;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+#[derive(Debug, Default)]
+pub struct Preprocessor {
+    dialect: Dialect,
+    macros: HashMap<String, Rc<Macro>>,
+    conditionals: Vec<Conditional>,
+}
+
+/// An open `#if`, `#ifdef` or `#ifndef`.
+#[derive(Debug)]
+struct Conditional {
+    pos: Pos,
+    /// The current branch is the one kept.
+    taking: bool,
+    /// A branch was already kept, or the whole conditional is skipped.
+    taken: bool,
+    seen_else: bool,
+}
+
+/// A token together with the names of the macros whose expansion produced
+/// it, which mustn't be expanded again.
+#[derive(Clone, Debug)]
+struct Token {
+    parsed: Parsed,
+    hide: Vec<String>,
+}
+
+impl Token {
+    fn new(parsed: Parsed) -> Self {
+        Self {
+            parsed,
+            hide: vec![],
+        }
+    }
+
+    fn pos(&self) -> Pos {
+        self.parsed.pos()
+    }
+
+    fn ident(&self) -> Option<&str> {
+        match &self.parsed.ty {
+            ParsedTy::Ident(ident) => Some(ident),
+            _ => None,
+        }
+    }
+
+    fn is_punct(&self, f: fn(&Punct) -> bool) -> bool {
+        matches!(&self.parsed.ty, ParsedTy::Punct(punct) if f(punct))
+    }
+
+    /// A `#` starting a directive, which only counts in the source itself.
+    fn starts_directive(&self) -> bool {
+        self.parsed.bol && self.hide.is_empty() && self.is_punct(|p| matches!(p, Punct::Hashtag(_)))
+    }
+}
+
+fn error(msg: impl std::fmt::Display, pos: Pos) -> Error<'static> {
+    Error::new_at(msg, None, pos)
+}
+
+/// The tokens' source spelling.
+fn spelling(ty: &ParsedTy) -> String {
+    match ty {
+        ParsedTy::Ident(ident) => ident.clone(),
+        ParsedTy::Literal(lit) => TokenTree::Literal(lit.clone()).to_string(),
+        ParsedTy::Punct(punct) => punct.to_string(),
+        ParsedTy::Group(delim) | ParsedTy::End(delim) => {
+            let (open, close) = match delim {
+                Delimeter::Paren => ("(", ")"),
+                Delimeter::Bracket => ("[", "]"),
+                Delimeter::Brace => ("{", "}"),
+            };
+            match ty {
+                ParsedTy::Group(_) => open,
+                _ => close,
+            }
+            .to_string()
+        }
+        ParsedTy::Comment(comment) => comment.clone(),
+    }
+}
+
+fn int(value: i128, at: &Token) -> Token {
+    Token {
+        parsed: Parsed {
+            ty: ParsedTy::Literal(Literal::Int(value, value.to_string())),
+            ..at.parsed.clone()
+        },
+        hide: at.hide.clone(),
+    }
+}
+
+impl Preprocessor {
+    pub fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            ..Default::default()
+        }
+    }
+
+    /// A preprocessor with the macros `target` predefines.
+    pub fn for_target(target: &TargetProfile, dialect: Dialect) -> Self {
+        let mut pp = Self::new(dialect);
+        for (name, value) in target.predefined_macros(dialect) {
+            pp.define(&format!("{name}={value}"))
+                .expect("predefined macros are valid");
+        }
+        pp
+    }
+
+    /// Defines a macro like `-D` does: `NAME` defines it as `1`, `NAME=VALUE`
+    /// and `NAME(ARGS)=VALUE` as the given value. Replaces any previous
+    /// definition.
+    pub fn define(&mut self, definition: &str) -> Result<()> {
+        let (name, value) = definition.split_once('=').unwrap_or((definition, "1"));
+        let tokens: Vec<_> = parse::lex(&format!("{name} {value}"), false)?
+            .into_iter()
+            .map(Token::new)
+            .collect();
+        self.define_macro(tokens, Pos::zero())
+    }
+
+    /// Removes a macro like `-U` does.
+    pub fn undefine(&mut self, name: &str) {
+        self.macros.remove(name);
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.macros.contains_key(name)
+    }
+
+    /// Preprocesses `input`. Macros defined by it stay defined for later
+    /// calls.
+    pub fn preprocess(&mut self, input: &str) -> Result<TokenStream> {
+        self.conditionals.clear();
+        let mut input: VecDeque<_> = parse::lex(input, false)?
+            .into_iter()
+            .map(Token::new)
+            .collect();
+        let mut out = vec![];
+        // the line of a `#pragma` from `_Pragma` ends before the next token
+        let mut end_line = false;
+        while let Some(token) = input.pop_front() {
+            if token.starts_directive() {
+                let line = take_line(&mut input);
+                if let Some(kept) = self.directive(token, line)? {
+                    out.extend(kept.into_iter().map(|token| token.parsed));
+                }
+            } else if self.skipping() {
+            } else if token.ident() == Some("_Pragma") {
+                out.extend(pragma_operator(&token, &mut input)?);
+                end_line = true;
+            } else if !self.expand(&token, &mut input)? {
+                let mut parsed = token.parsed;
+                parsed.bol |= std::mem::take(&mut end_line);
+                out.push(parsed);
+            }
+        }
+        if let Some(conditional) = self.conditionals.pop() {
+            return Err(error("unterminated conditional directive", conditional.pos));
+        }
+        Ok(TokenStream::new(parse::parsed_into_boxed_entries(out)?))
+    }
+
+    fn skipping(&self) -> bool {
+        self.conditionals.last().is_some_and(|c| !c.taking)
+    }
+
+    /// Handles the directive started by `hash`, returning it if it has to be
+    /// kept in the output.
+    fn directive(&mut self, hash: Token, line: Vec<Token>) -> Result<Option<Vec<Token>>> {
+        let Some(name) = line.first().and_then(Token::ident).map(str::to_string) else {
+            // the null directive, or garbage in a skipped block
+            return match line.first() {
+                Some(token) if !self.skipping() => {
+                    Err(error("invalid preprocessing directive", token.pos()))
+                }
+                _ => Ok(None),
+            };
+        };
+        let pos = line[0].pos();
+        let rest = line[1..].to_vec();
+        match name.as_str() {
+            "if" | "ifdef" | "ifndef" => {
+                let taking = !self.skipping() && self.condition(&name, rest, &pos)?;
+                self.conditionals.push(Conditional {
+                    pos: hash.pos(),
+                    taking,
+                    taken: taking || self.skipping(),
+                    seen_else: false,
+                });
+            }
+            "elif" | "elifdef" | "elifndef" | "else" => {
+                let Some(conditional) = self.conditionals.last() else {
+                    return Err(error(format!("#{name} without #if"), pos));
+                };
+                if conditional.seen_else {
+                    return Err(error(format!("#{name} after #else"), pos));
+                }
+                let taking = !conditional.taken
+                    && (name == "else" || self.condition(&name[2..], rest, &pos)?);
+                let conditional = self.conditionals.last_mut().unwrap();
+                conditional.taking = taking;
+                conditional.taken |= taking;
+                conditional.seen_else = name == "else";
+            }
+            "endif" => {
+                if self.conditionals.pop().is_none() {
+                    return Err(error("#endif without #if", pos));
+                }
+            }
+            _ if self.skipping() => {}
+            "define" => self.define_macro(rest, pos)?,
+            "undef" => {
+                let name = macro_name(&rest, "#undef", pos)?;
+                self.macros.remove(name);
+            }
+            "error" => {
+                let msg: Vec<_> = rest.iter().map(|t| spelling(&t.parsed.ty)).collect();
+                return Err(error(format!("#error {}", msg.join(" ")), hash.pos()));
+            }
+            "warning" => {}
+            _ => {
+                let mut kept = vec![hash];
+                kept.extend(line);
+                return Ok(Some(kept));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Evaluates the condition of an `#if`, `#ifdef` or `#ifndef` (or their
+    /// `#elif` counterparts, with `name` stripped of `el`).
+    fn condition(&mut self, name: &str, rest: Vec<Token>, pos: &Pos) -> Result<bool> {
+        match name {
+            "if" => self.eval(rest, pos),
+            "ifdef" => Ok(self.is_defined(macro_name(&rest, "#ifdef", pos.clone())?)),
+            _ => Ok(!self.is_defined(macro_name(&rest, "#ifndef", pos.clone())?)),
+        }
+    }
+}
+
+/// Turns `_Pragma("...")` into the equivalent `#pragma` line.
+fn pragma_operator(token: &Token, input: &mut VecDeque<Token>) -> Result<Vec<Parsed>> {
+    let mut next = || input.pop_front().map(|token| token.parsed.ty);
+    let str = match (next(), next(), next()) {
+        (
+            Some(ParsedTy::Group(Delimeter::Paren)),
+            Some(ParsedTy::Literal(Literal::Str(str))),
+            Some(ParsedTy::End(Delimeter::Paren)),
+        ) => str,
+        _ => {
+            return Err(error(
+                "_Pragma takes a parenthesized string literal",
+                token.pos(),
+            ))
+        }
+    };
+    let str = str.replace("\\\"", "\"").replace("\\\\", "\\");
+    let at = |ty, bol, space| Parsed {
+        bol,
+        space,
+        ty,
+        ..token.parsed.clone()
+    };
+    let mut line = vec![
+        at(ParsedTy::Punct(Punct::Hashtag(Hashtag)), true, true),
+        at(ParsedTy::Ident("pragma".to_string()), false, false),
+    ];
+    for parsed in parse::lex(&str, false)? {
+        line.push(at(parsed.ty, false, true));
+    }
+    Ok(line)
+}
+
+/// Removes the tokens up to the end of the current line from `input`.
+fn take_line(input: &mut VecDeque<Token>) -> Vec<Token> {
+    let mut line = vec![];
+    while let Some(token) = input.pop_front() {
+        if token.parsed.bol {
+            input.push_front(token);
+            break;
+        }
+        line.push(token);
+    }
+    line
+}
+
+fn macro_name<'a>(rest: &'a [Token], directive: &str, pos: Pos) -> Result<&'a str> {
+    match rest.first() {
+        Some(token) => match token.ident() {
+            Some(ident) => Ok(ident),
+            None => Err(error("macro names must be identifiers", token.pos())),
+        },
+        None => Err(error(
+            format!("no macro name given in {directive} directive"),
+            pos,
+        )),
+    }
+}
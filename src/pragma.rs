@@ -0,0 +1,120 @@
+use crate::{Ident, Parse, ParseStream, Result, TokenStream};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+ast_struct! {
+    /// `#pragma GCC diagnostic push`, also produced by `_Pragma("...")`.
+    pub struct Pragma {
+        /// `GCC` in `#pragma GCC diagnostic push`, `None` for
+        /// `#pragma once`.
+        pub namespace: Option<Ident>,
+        pub name: Ident,
+        pub args: TokenStream,
+        /// Set by the parser registered for the pragma, see
+        /// [`PragmaRegistry`].
+        pub parsed: Option<Rc<dyn Any>>,
+    }
+}
+
+/// Namespaces compilers commonly use, a first word registered with
+/// [`PragmaRegistry`] counts as one too.
+const NAMESPACES: &[&str] = &["GCC", "clang", "STDC", "omp", "acc"];
+
+type PragmaParser = dyn for<'a> Fn(ParseStream<'a>) -> Result<Rc<dyn Any>>;
+
+/// Typed parsers for pragmas, keyed by their first word: the namespace, or
+/// the name for pragmas without one. A parser gets the tokens following that
+/// word and has to consume all of them; the result is available through
+/// [`Pragma::get`].
+#[derive(Clone, Default)]
+pub struct PragmaRegistry {
+    parsers: HashMap<String, Rc<PragmaParser>>,
+}
+
+impl PragmaRegistry {
+    pub fn register<T: Parse + 'static>(&mut self, first: &str) {
+        self.parsers.insert(
+            first.to_string(),
+            Rc::new(|parse: ParseStream| Ok(Rc::new(T::parse(parse)?) as Rc<dyn Any>)),
+        );
+    }
+
+    fn get(&self, first: &str) -> Option<&Rc<PragmaParser>> {
+        self.parsers.get(first)
+    }
+}
+
+impl Debug for PragmaRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.parsers.keys()).finish()
+    }
+}
+
+impl Pragma {
+    /// The result of the registered parser, if it produced a `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.parsed.as_ref()?.downcast_ref()
+    }
+}
+
+impl Parse for Pragma {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let (directive, content) = crate::directive::directive(parse)?;
+        if directive != "pragma" {
+            return Err(parse.error("expected #pragma"));
+        }
+        let first = content.call(Ident::parse_any)?;
+        let parser = content.config().pragmas.get(first.as_str()).cloned();
+        let parsed = match parser {
+            Some(ref parser) => {
+                let fork = content.fork();
+                let parsed = parser(&fork)?;
+                if !fork.is_empty() {
+                    return Err(
+                        fork.error(format!("unexpected token in #pragma {}", first.as_str()))
+                    );
+                }
+                Some(parsed)
+            }
+            None => None,
+        };
+        let namespace = NAMESPACES.contains(&first.as_str()) || parser.is_some();
+        let (namespace, name) = if namespace && content.cursor().ident().is_some() {
+            (Some(first), content.call(Ident::parse_any)?)
+        } else {
+            (None, first)
+        };
+        Ok(Self {
+            namespace,
+            name,
+            args: content.cursor().token_stream(),
+            parsed,
+        })
+    }
+}
+
+mod quote {
+    use super::*;
+    use crate::tokens::TokenTree;
+    use crate::{to_tokens, ToTokens};
+
+    impl ToTokens for Pragma {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                namespace,
+                name,
+                args,
+                parsed: _,
+            } = self;
+            to_tokens::directive(&to_tokens::multiple(|tokens| {
+                tokens.extend_one(TokenTree::Ident("pragma".to_string()));
+                namespace.to_tokens(tokens);
+                name.to_tokens(tokens);
+                args.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+        }
+    }
+}
@@ -1,18 +1,20 @@
+use crate::block::BlockItem;
 use crate::{tokens, Expr, Stmnt};
 
 ast_struct! {
     pub struct Switch {
+        pub switch_kw: token![switch],
         pub paren: tokens::Paren,
         pub selection: Box<Expr>,
-        pub stmnt: Box<SwitchLabel>,
+        pub stmnt: Box<Stmnt>,
     }
 }
 
-ast_enum! {
-    pub enum SwitchLabel {
-        Case(Case),
-        Default(Default)
-    }
+/// A `case` or `default` label of a [`Switch`].
+#[derive(Clone, Copy)]
+pub enum SwitchLabel<'a> {
+    Case(&'a Case),
+    Default(&'a Default),
 }
 
 ast_struct! {
@@ -34,6 +36,7 @@ ast_struct! {
 
 ast_struct! {
     pub struct If {
+        pub if_kw: token![if],
         pub paren: tokens::Paren,
         pub condition: Box<Expr>,
         pub stmnt: Box<Stmnt>,
@@ -48,37 +51,105 @@ ast_struct! {
     }
 }
 
-use crate::{Parse, ParseStream, Result};
+impl Switch {
+    /// The `case` and `default` labels of this switch in source order,
+    /// excluding those of nested switches.
+    pub fn labels(&self) -> Vec<SwitchLabel<'_>> {
+        let mut labels = Vec::new();
+        collect_labels(&self.stmnt, &mut labels);
+        labels
+    }
 
-impl Parse for Switch {
-    fn parse(parse: ParseStream) -> Result<Self> {
-        let content;
-        Ok(Self {
-            paren: parenthesized!(content in parse)?,
-            selection: content.parse()?,
-            stmnt: parse.parse()?,
+    /// The constant expressions of the `case` labels.
+    pub fn case_values(&self) -> impl Iterator<Item = &Expr> {
+        self.labels().into_iter().filter_map(|label| match label {
+            SwitchLabel::Case(case) => Some(case.value()),
+            SwitchLabel::Default(_) => None,
         })
     }
 }
 
-impl Parse for SwitchLabel {
+impl Case {
+    pub fn value(&self) -> &Expr {
+        &self.const_expr
+    }
+}
+
+fn collect_labels<'a>(stmnt: &'a Stmnt, labels: &mut Vec<SwitchLabel<'a>>) {
+    match stmnt {
+        Stmnt::Case(case) => {
+            labels.push(SwitchLabel::Case(case));
+            collect_labels(&case.stmnt, labels);
+        }
+        Stmnt::Default(default) => {
+            labels.push(SwitchLabel::Default(default));
+            collect_labels(&default.stmnt, labels);
+        }
+        Stmnt::Label(label) => collect_labels(&label.stmnt, labels),
+        Stmnt::Block(block) => collect_block_labels(&block.items, labels),
+        Stmnt::If(if_stmnt) => {
+            collect_labels(&if_stmnt.stmnt, labels);
+            if let Some(else_stmnt) = &if_stmnt.else_stmnt {
+                collect_labels(&else_stmnt.stmnt, labels);
+            }
+        }
+        Stmnt::While(s) => collect_labels(&s.stmnt, labels),
+        Stmnt::DoWhile(s) => collect_labels(&s.stmnt, labels),
+        Stmnt::For(s) => collect_labels(&s.stmnt, labels),
+        // labels of a nested switch belong to it
+        Stmnt::Switch(_)
+        | Stmnt::Expr(_)
+        | Stmnt::Goto(_)
+        | Stmnt::Continue(_)
+        | Stmnt::Break(_)
+        | Stmnt::Return(_)
+        | Stmnt::Null(_) => {}
+    }
+}
+
+fn collect_block_labels<'a>(items: &'a [BlockItem], labels: &mut Vec<SwitchLabel<'a>>) {
+    for item in items {
+        match item {
+            BlockItem::Stmnt(stmnt) => collect_labels(stmnt, labels),
+            BlockItem::Conditional(conditional) => {
+                for branch in &conditional.branches {
+                    collect_block_labels(&branch.items, labels);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+use crate::expr::parsing::parse_assign;
+use crate::{Parse, ParseStream, Result};
+
+impl Parse for Switch {
     fn parse(parse: ParseStream) -> Result<Self> {
-        let lookahead = parse.lookahead1();
-        Ok(if lookahead.peek::<token![case]>() {
-            Self::Case(parse.parse()?)
-        } else if lookahead.peek::<token![default]>() {
-            Self::Default(parse.parse()?)
-        } else {
-            return Err(lookahead.error());
+        let content;
+        let switch_kw = parse.parse()?;
+        let paren = parenthesized!(content in parse)?;
+        let selection = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after switch expression"));
+        }
+        Ok(Self {
+            switch_kw,
+            paren,
+            selection,
+            stmnt: parse.switch_body(|parse| parse.parse())?,
         })
     }
 }
 
 impl Parse for Case {
     fn parse(parse: ParseStream) -> Result<Self> {
+        if !parse.in_switch() {
+            return Err(parse.error("`case` label not within a switch statement"));
+        }
         Ok(Self {
             case_kw: parse.parse()?,
-            const_expr: parse.parse()?,
+            const_expr: Box::new(parse_assign(parse)?),
             colon: parse.parse()?,
             stmnt: parse.parse()?,
         })
@@ -87,6 +158,9 @@ impl Parse for Case {
 
 impl Parse for Default {
     fn parse(parse: ParseStream) -> Result<Self> {
+        if !parse.in_switch() {
+            return Err(parse.error("`default` label not within a switch statement"));
+        }
         Ok(Self {
             default_kw: parse.parse()?,
             colon: parse.parse()?,
@@ -98,9 +172,16 @@ impl Parse for Default {
 impl Parse for If {
     fn parse(parse: ParseStream) -> Result<Self> {
         let content;
+        let if_kw = parse.parse()?;
+        let paren = parenthesized!(content in parse)?;
+        let condition = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after if condition"));
+        }
         Ok(Self {
-            paren: parenthesized!(content in parse)?,
-            condition: content.parse()?,
+            if_kw,
+            paren,
+            condition,
             stmnt: parse.parse()?,
             else_stmnt: parse.parse()?,
         })
@@ -117,30 +198,23 @@ impl Parse for Else {
 }
 
 mod quote {
-    use super::{Case, Default, Else, If, Switch, SwitchLabel};
+    use super::{Case, Default, Else, If, Switch};
     use crate::{to_tokens, ToTokens, TokenStream};
 
     impl ToTokens for Switch {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let Self {
+                switch_kw,
                 paren: _,
                 selection,
                 stmnt,
             } = self;
+            switch_kw.to_tokens(tokens);
             to_tokens::parenthesized(selection).to_tokens(tokens);
             stmnt.to_tokens(tokens);
         }
     }
 
-    impl ToTokens for SwitchLabel {
-        fn to_tokens(&self, tokens: &mut TokenStream) {
-            match self {
-                SwitchLabel::Case(s) => s.to_tokens(tokens),
-                SwitchLabel::Default(s) => s.to_tokens(tokens),
-            }
-        }
-    }
-
     impl ToTokens for Case {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let Self {
@@ -172,11 +246,13 @@ mod quote {
     impl ToTokens for If {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let Self {
+                if_kw,
                 paren: _,
                 condition,
                 stmnt,
                 else_stmnt,
             } = self;
+            if_kw.to_tokens(tokens);
             to_tokens::parenthesized(condition).to_tokens(tokens);
             stmnt.to_tokens(tokens);
             else_stmnt.to_tokens(tokens);
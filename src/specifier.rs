@@ -13,7 +13,7 @@ ast_struct! {
     }
 }
 
-ast_enum_simple! {
+ast_enum! {
     pub enum TypeSpecifier {
         Void(token![void]),
         Char(token![char]),
@@ -26,6 +26,20 @@ ast_enum_simple! {
         Unsigned(token![unsigned]),
         Bool(token![_Bool]),
         Complex(token![_Complex]),
+        Struct(StructSpecifier),
+        Union(UnionSpecifier),
+        Enum(EnumSpecifier),
+        Atomic(AtomicSpecifier),
+        TypedefName(Ident),
+    }
+}
+
+ast_struct! {
+    /// `_Atomic(struct node *)`, as opposed to the qualifier in `_Atomic int`.
+    pub struct AtomicSpecifier {
+        pub atomic: token![_Atomic],
+        pub paren: tokens::Paren,
+        pub ty: TypeName,
     }
 }
 
@@ -63,8 +77,26 @@ ast_struct! {
     }
 }
 
+ast_struct! {
+    /// `_Alignas(double)` or `_Alignas(16)`, spelled `alignas` since C23.
+    pub struct AlignmentSpecifier {
+        pub keyword: AlignasKeyword,
+        pub paren: tokens::Paren,
+        pub arg: AlignasArg,
+    }
+}
+
+ast_enum_simple! {
+    pub enum AlignasKeyword {
+        Alignas(token![_Alignas]),
+        C23(token![alignas]),
+    }
+}
+
 ast_enum! {
-    pub enum AlignmentSpecifier {
+    pub enum AlignasArg {
+        Type(TypeName),
+        Expr(Box<Expr>),
     }
 }
 
@@ -74,11 +106,128 @@ ast_struct! {
     }
 }
 
-use crate::{parse::parse_into_vec, Parse, ParseStream, Result};
+impl AlignmentSpecifier {
+    /// The type whose alignment is requested, `double` in `_Alignas(double)`.
+    pub fn type_name(&self) -> Option<&TypeName> {
+        match &self.arg {
+            AlignasArg::Type(ty) => Some(ty),
+            AlignasArg::Expr(_) => None,
+        }
+    }
+
+    /// The requested alignment, `16` in `_Alignas(16)`.
+    pub fn expr(&self) -> Option<&Expr> {
+        match &self.arg {
+            AlignasArg::Type(_) => None,
+            AlignasArg::Expr(expr) => Some(expr),
+        }
+    }
+}
+
+use crate::enumeration::EnumSpecifier;
+use crate::expr::parsing::parse_assign;
+use crate::struct_union::{StructSpecifier, UnionSpecifier};
+use crate::{parse::parse_into_vec, tokens, Expr, Ident, Parse, ParseStream, Result, TypeName};
+
+fn starts_tag(parse: ParseStream) -> bool {
+    parse.peek::<token![struct]>() || parse.peek::<token![union]>() || parse.peek::<token![enum]>()
+}
+
+fn starts_alignment(parse: ParseStream) -> bool {
+    parse.peek::<token![_Alignas]>() || parse.peek::<token![alignas]>()
+}
+
+/// `_Atomic` followed by a parenthesis is always the specifier.
+fn starts_atomic_specifier(parse: ParseStream) -> bool {
+    parse.peek::<token![_Atomic]>() && parse.peek2::<tokens::Paren>()
+}
+
+/// A keyword that can only start a specifier, so whatever goes wrong after
+/// it is the error to report.
+fn commits_to_specifier(parse: ParseStream) -> bool {
+    starts_tag(parse) || starts_alignment(parse) || starts_atomic_specifier(parse)
+}
+
+/// Whether a specifier comes next, which can never start an expression.
+pub(crate) fn peek_specifier(parse: ParseStream) -> bool {
+    commits_to_specifier(parse) || parse.fork().parse::<Specifier>().is_ok()
+}
+
+fn is_typedef_name(parse: ParseStream) -> bool {
+    match parse.cursor().ident() {
+        Some((ident, _)) => parse.is_typedef_name(ident),
+        None => false,
+    }
+}
+
+impl Parse for TypeSpecifier {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let lookahead = parse.lookahead1();
+        Ok(if lookahead.peek::<token![void]>() {
+            Self::Void(parse.parse()?)
+        } else if lookahead.peek::<token![char]>() {
+            Self::Char(parse.parse()?)
+        } else if lookahead.peek::<token![short]>() {
+            Self::Short(parse.parse()?)
+        } else if lookahead.peek::<token![int]>() {
+            Self::Int(parse.parse()?)
+        } else if lookahead.peek::<token![long]>() {
+            Self::Long(parse.parse()?)
+        } else if lookahead.peek::<token![float]>() {
+            Self::Float(parse.parse()?)
+        } else if lookahead.peek::<token![double]>() {
+            Self::Double(parse.parse()?)
+        } else if lookahead.peek::<token![signed]>() {
+            Self::Signed(parse.parse()?)
+        } else if lookahead.peek::<token![unsigned]>() {
+            Self::Unsigned(parse.parse()?)
+        } else if lookahead.peek::<token![_Bool]>() {
+            Self::Bool(parse.parse()?)
+        } else if lookahead.peek::<token![_Complex]>() {
+            Self::Complex(parse.parse()?)
+        } else if lookahead.peek::<token![struct]>() {
+            Self::Struct(parse.parse()?)
+        } else if lookahead.peek::<token![union]>() {
+            Self::Union(parse.parse()?)
+        } else if lookahead.peek::<token![enum]>() {
+            Self::Enum(parse.parse()?)
+        } else if starts_atomic_specifier(parse) {
+            let content;
+            let atomic = AtomicSpecifier {
+                atomic: parse.parse()?,
+                paren: parenthesized!(content in parse)?,
+                ty: content.parse()?,
+            };
+            if !content.is_empty() {
+                return Err(content.error("expected `)` after type name"));
+            }
+            Self::Atomic(atomic)
+        } else if is_typedef_name(parse) {
+            Self::TypedefName(parse.parse()?)
+        } else {
+            return Err(lookahead.error());
+        })
+    }
+}
 
 impl Parse for AlignmentSpecifier {
     fn parse(parse: ParseStream) -> Result<Self> {
-        Err(parse.error("unsupported alignment specifier"))
+        let keyword = parse.parse()?;
+        let content;
+        let paren = parenthesized!(content in parse)?;
+        let arg = if peek_specifier(&content) {
+            AlignasArg::Type(content.parse()?)
+        } else {
+            AlignasArg::Expr(Box::new(parse_assign(&content)?))
+        };
+        if !content.is_empty() {
+            return Err(content.error("expected `)` after alignment"));
+        }
+        Ok(Self {
+            keyword,
+            paren,
+            arg,
+        })
     }
 }
 
@@ -121,10 +270,12 @@ impl Parse for Specifier {
     fn parse(parse: ParseStream) -> Result<Self> {
         Ok(if parse.fork().parse::<FunctionSpecifier>().is_ok() {
             Self::Function(parse.parse()?)
-        } else if parse.fork().parse::<AlignmentSpecifier>().is_ok() {
+        } else if starts_alignment(parse) {
             Self::Alignment(parse.parse()?)
-        } else if parse.fork().parse::<TypeSpecifier>().is_ok() {
+        } else if starts_tag(parse) || starts_atomic_specifier(parse) {
             Self::Type(parse.parse()?)
+        } else if let Some(specifier) = parse.parse::<Option<TypeSpecifier>>()? {
+            Self::Type(specifier)
         } else if parse.fork().parse::<TypeQualifier>().is_ok() {
             Self::Qualifier(parse.parse()?)
         } else if parse.fork().parse::<StorageClass>().is_ok() {
@@ -136,15 +287,76 @@ impl Parse for Specifier {
 }
 
 ast_struct! {
-    pub struct SpecifierList {
+    /// `static const unsigned long`, shared by all declarators of a declaration.
+    pub struct DeclSpecifiers {
         pub items: Vec<Specifier>
     }
 }
 
-impl Parse for SpecifierList {
+impl DeclSpecifiers {
+    /// Whether the type is atomic, by either `_Atomic(T)` or `_Atomic T`.
+    pub fn is_atomic(&self) -> bool {
+        self.items.iter().any(|specifier| {
+            matches!(
+                specifier,
+                Specifier::Type(TypeSpecifier::Atomic(_))
+                    | Specifier::Qualifier(TypeQualifier::Atomic(_))
+            )
+        })
+    }
+
+    /// The alignment specifiers; the strictest alignment applies.
+    pub fn alignments(&self) -> impl Iterator<Item = &AlignmentSpecifier> {
+        self.items.iter().filter_map(|specifier| match specifier {
+            Specifier::Alignment(alignment) => Some(alignment),
+            _ => None,
+        })
+    }
+}
+
+impl Parse for DeclSpecifiers {
     fn parse(parse: ParseStream) -> Result<Self> {
+        DeclSpecifiers::parse_list(parse, false)
+    }
+}
+
+impl DeclSpecifiers {
+    /// The specifiers of a type name, which can't have a storage class or
+    /// function specifier: `(int)x`, but not `(static int)x`.
+    pub(crate) fn parse_type_name(parse: ParseStream) -> Result<Self> {
+        DeclSpecifiers::parse_list(parse, true)
+    }
+
+    fn parse_list(parse: ParseStream, type_name: bool) -> Result<Self> {
         let mut items = vec![];
-        while let Ok(specifier) = parse.parse() {
+        let mut has_type = false;
+        loop {
+            let fork = parse.fork();
+            let specifier = match fork.parse::<Specifier>() {
+                Ok(specifier) => specifier,
+                Err(err) if commits_to_specifier(parse) => return Err(err),
+                Err(_) => break,
+            };
+            // a typedef name following another type specifier is the
+            // declarator, as in `typedef int T; { long T; }`
+            if has_type && matches!(specifier, Specifier::Type(TypeSpecifier::TypedefName(_))) {
+                break;
+            }
+            match specifier {
+                Specifier::Storage(_) if type_name => {
+                    return Err(
+                        parse.error("type name does not allow storage class to be specified")
+                    )
+                }
+                Specifier::Function(_) if type_name => {
+                    return Err(
+                        parse.error("type name does not allow function specifier to be specified")
+                    )
+                }
+                _ => {}
+            }
+            has_type |= matches!(specifier, Specifier::Type(_));
+            parse.set(fork);
             items.push(specifier)
         }
         if items.is_empty() {
@@ -157,7 +369,7 @@ impl Parse for SpecifierList {
 
 mod quote {
     use super::*;
-    use crate::{ToTokens, TokenStream};
+    use crate::{to_tokens, ToTokens, TokenStream};
 
     impl ToTokens for TypeQualifier {
         fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -184,10 +396,27 @@ mod quote {
                 TypeSpecifier::Unsigned(t) => t.to_tokens(tokens),
                 TypeSpecifier::Bool(t) => t.to_tokens(tokens),
                 TypeSpecifier::Complex(t) => t.to_tokens(tokens),
+                TypeSpecifier::Struct(t) => t.to_tokens(tokens),
+                TypeSpecifier::Union(t) => t.to_tokens(tokens),
+                TypeSpecifier::Enum(t) => t.to_tokens(tokens),
+                TypeSpecifier::Atomic(t) => t.to_tokens(tokens),
+                TypeSpecifier::TypedefName(t) => t.to_tokens(tokens),
             }
         }
     }
 
+    impl ToTokens for AtomicSpecifier {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                atomic,
+                paren: _,
+                ty,
+            } = self;
+            atomic.to_tokens(tokens);
+            to_tokens::parenthesized(ty).to_tokens(tokens);
+        }
+    }
+
     impl ToTokens for FunctionSpecifier {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             match self {
@@ -209,7 +438,33 @@ mod quote {
     }
 
     impl ToTokens for AlignmentSpecifier {
-        fn to_tokens(&self, tokens: &mut TokenStream) {}
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                keyword,
+                paren: _,
+                arg,
+            } = self;
+            keyword.to_tokens(tokens);
+            to_tokens::parenthesized(arg).to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for AlignasKeyword {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                AlignasKeyword::Alignas(t) => t.to_tokens(tokens),
+                AlignasKeyword::C23(t) => t.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for AlignasArg {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                AlignasArg::Type(e) => e.to_tokens(tokens),
+                AlignasArg::Expr(e) => e.to_tokens(tokens),
+            }
+        }
     }
 
     impl ToTokens for Specifier {
@@ -239,5 +494,5 @@ mod quote {
         };
     }
 
-    impl_list!(TypeQualifierList TypeSpecifierList FunctionSpecifierList StorageClassList AlignmentSpecifierList SpecifierList);
+    impl_list!(TypeQualifierList TypeSpecifierList FunctionSpecifierList StorageClassList AlignmentSpecifierList DeclSpecifiers);
 }
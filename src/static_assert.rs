@@ -0,0 +1,74 @@
+use crate::lit::LitStr;
+use crate::{tokens, Expr};
+
+ast_struct! {
+    /// `_Static_assert(sizeof(int) == 4, "int is 32 bits");`
+    pub struct StaticAssert {
+        pub static_assert: token![_Static_assert],
+        pub paren: tokens::Paren,
+        pub expr: Box<Expr>,
+        pub message: Option<(token![,], LitStr)>,
+        pub semi_colon: token![;],
+    }
+}
+
+impl StaticAssert {
+    /// The diagnostic message, optional since C23.
+    pub fn message(&self) -> Option<&LitStr> {
+        self.message.as_ref().map(|(_, message)| message)
+    }
+}
+
+use crate::expr::parsing::parse_assign;
+use crate::{Parse, ParseStream, Result};
+
+impl Parse for StaticAssert {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let static_assert = parse.parse()?;
+        let content;
+        let paren = parenthesized!(content in parse)?;
+        let expr = Box::new(content.call(parse_assign)?);
+        let message = if content.peek::<token![,]>() {
+            Some((content.parse()?, content.parse()?))
+        } else {
+            None
+        };
+        if !content.is_empty() {
+            return Err(content.error("expected `,` or `)` in _Static_assert"));
+        }
+        Ok(Self {
+            static_assert,
+            paren,
+            expr,
+            message,
+            semi_colon: parse.parse()?,
+        })
+    }
+}
+
+mod quote {
+    use super::*;
+    use crate::{to_tokens, ToTokens, TokenStream};
+
+    impl ToTokens for StaticAssert {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                static_assert,
+                paren: _,
+                expr,
+                message,
+                semi_colon,
+            } = self;
+            static_assert.to_tokens(tokens);
+            to_tokens::parenthesized(&to_tokens::multiple(|tokens| {
+                expr.to_tokens(tokens);
+                if let Some((comma, message)) = message {
+                    comma.to_tokens(tokens);
+                    message.to_tokens(tokens);
+                }
+            }))
+            .to_tokens(tokens);
+            semi_colon.to_tokens(tokens);
+        }
+    }
+}
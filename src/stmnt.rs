@@ -1,8 +1,13 @@
-use crate::{Block, Break, Continue, DoWhile, Expr, For, Goto, If, Label, Return, Switch, While};
+use crate::{
+    Block, Break, Case, Continue, Default, DoWhile, Expr, For, Goto, If, Label, Return, Switch,
+    While,
+};
 
 ast_enum! {
     pub enum Stmnt {
         Label(Label),
+        Case(Case),
+        Default(Default),
         Block(Block),
         Expr(StmntExpr),
         Switch(Switch),
@@ -14,6 +19,8 @@ ast_enum! {
         Continue(Continue),
         Break(Break),
         Return(Return),
+        /// `;`
+        Null(token![;]),
     }
 }
 
@@ -30,8 +37,12 @@ impl Parse for Stmnt {
     fn parse(parse: ParseStream) -> Result<Self> {
         Ok(if parse.peek::<token![if]>() {
             Self::If(parse.parse()?)
+        } else if parse.peek::<token![case]>() {
+            Self::Case(parse.parse()?)
+        } else if parse.peek::<token![default]>() {
+            Self::Default(parse.parse()?)
         } else if parse.peek::<token![switch]>() {
-            Self::While(parse.parse()?)
+            Self::Switch(parse.parse()?)
         } else if parse.peek::<token![goto]>() {
             Self::Goto(parse.parse()?)
         } else if parse.peek::<token![continue]>() {
@@ -42,14 +53,18 @@ impl Parse for Stmnt {
             Self::Return(parse.parse()?)
         } else if parse.peek::<token![while]>() {
             Self::While(parse.parse()?)
+        } else if parse.peek::<token![do]>() {
+            Self::DoWhile(parse.parse()?)
+        } else if parse.peek::<token![for]>() {
+            Self::For(parse.parse()?)
+        } else if parse.peek::<token![;]>() {
+            Self::Null(parse.parse()?)
+        } else if parse.peek::<tokens::Brace>() {
+            Self::Block(parse.parse()?)
+        } else if parse.peek::<Ident>() && parse.peek2::<token![:]>() {
+            Self::Label(parse.parse()?)
         } else {
-            if parse.peek::<tokens::Brace>() {
-                Self::Block(parse.parse()?)
-            } else if parse.peek::<Ident>() && parse.peek2::<token![:]>() {
-                Self::Label(parse.parse()?)
-            } else {
-                Self::Expr(parse.parse()?)
-            }
+            Self::Expr(parse.parse()?)
         })
     }
 }
@@ -71,6 +86,8 @@ mod quote {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             match self {
                 Stmnt::Label(s) => s.to_tokens(tokens),
+                Stmnt::Case(s) => s.to_tokens(tokens),
+                Stmnt::Default(s) => s.to_tokens(tokens),
                 Stmnt::Block(s) => s.to_tokens(tokens),
                 Stmnt::Expr(s) => s.to_tokens(tokens),
                 Stmnt::Switch(s) => s.to_tokens(tokens),
@@ -82,6 +99,7 @@ mod quote {
                 Stmnt::Continue(s) => s.to_tokens(tokens),
                 Stmnt::Break(s) => s.to_tokens(tokens),
                 Stmnt::Return(s) => s.to_tokens(tokens),
+                Stmnt::Null(s) => s.to_tokens(tokens),
             }
         }
     }
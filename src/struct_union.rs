@@ -0,0 +1,308 @@
+use crate::declr::{Declarator, Derivation};
+use crate::specifier::{DeclSpecifiers, Specifier, TypeSpecifier};
+use crate::static_assert::StaticAssert;
+use crate::{tokens, Expr, Ident, Punctuated};
+
+ast_struct! {
+    /// `struct point { int x, y; }`, or a reference to a tag, `struct point`.
+    pub struct StructSpecifier {
+        pub struct_tk: token![struct],
+        pub tag: Option<Ident>,
+        pub members: Option<MemberList>,
+    }
+}
+
+ast_struct! {
+    /// `union value { int i; float f; }`, or a reference to a tag, `union value`.
+    pub struct UnionSpecifier {
+        pub union_tk: token![union],
+        pub tag: Option<Ident>,
+        pub members: Option<MemberList>,
+    }
+}
+
+ast_struct! {
+    pub struct MemberList {
+        pub brace: tokens::Brace,
+        pub items: Vec<Member>,
+    }
+}
+
+ast_enum! {
+    pub enum Member {
+        Field(MemberDeclr),
+        StaticAssert(StaticAssert),
+    }
+}
+
+ast_struct! {
+    /// `unsigned a : 3, b;`, or an anonymous `union { int i; float f; };`.
+    pub struct MemberDeclr {
+        pub specifiers: DeclSpecifiers,
+        pub declarators: Punctuated<MemberDeclarator, token![,]>,
+        pub semi_colon: token![;],
+    }
+}
+
+ast_struct! {
+    /// The declarator is missing for unnamed bit-fields, `int : 0;`.
+    pub struct MemberDeclarator {
+        pub declarator: Option<Declarator>,
+        pub bit_field: Option<BitField>,
+    }
+}
+
+ast_struct! {
+    pub struct BitField {
+        pub colon: token![:],
+        pub width: Box<Expr>,
+    }
+}
+
+impl StructSpecifier {
+    /// A definition, as opposed to a reference to a tag.
+    pub fn is_definition(&self) -> bool {
+        self.members.is_some()
+    }
+}
+
+impl UnionSpecifier {
+    /// A definition, as opposed to a reference to a tag.
+    pub fn is_definition(&self) -> bool {
+        self.members.is_some()
+    }
+}
+
+impl MemberList {
+    /// The flexible array member, `char data[];` as the last member. `data`
+    /// has to be declared as an array itself, not `char (*data)[];`.
+    pub fn flexible_array(&self) -> Option<&MemberDeclarator> {
+        let last = self.items.iter().rev().find_map(|member| match member {
+            Member::Field(field) => Some(field),
+            Member::StaticAssert(_) => None,
+        })?;
+        let member = last.declarators.iter().last()?;
+        let derivations = member.declarator.as_ref()?.derivations();
+        matches!(
+            derivations.first(),
+            Some(Derivation::Array {
+                is_static: false,
+                qualifier_list: None,
+                is_star: false,
+                len: None,
+            })
+        )
+        .then_some(member)
+    }
+}
+
+impl MemberDeclr {
+    /// A C11 anonymous struct or union: an untagged definition without
+    /// declarators whose members belong to the enclosing struct or union.
+    pub fn anonymous(&self) -> Option<&MemberList> {
+        if !self.declarators.is_empty() {
+            return None;
+        }
+        self.specifiers
+            .items
+            .iter()
+            .find_map(|specifier| match specifier {
+                Specifier::Type(TypeSpecifier::Struct(StructSpecifier {
+                    tag: None,
+                    members: Some(members),
+                    ..
+                }))
+                | Specifier::Type(TypeSpecifier::Union(UnionSpecifier {
+                    tag: None,
+                    members: Some(members),
+                    ..
+                })) => Some(members),
+                _ => None,
+            })
+    }
+}
+
+impl MemberDeclarator {
+    pub fn ident(&self) -> Option<&Ident> {
+        self.declarator.as_ref().map(Declarator::ident)
+    }
+}
+
+use crate::expr::parsing::parse_assign;
+use crate::{Parse, ParseStream, Result};
+
+impl Parse for StructSpecifier {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let struct_tk = parse.parse()?;
+        let (tag, members) = parse_tag_and_members(parse)?;
+        Ok(Self {
+            struct_tk,
+            tag,
+            members,
+        })
+    }
+}
+
+impl Parse for UnionSpecifier {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let union_tk = parse.parse()?;
+        let (tag, members) = parse_tag_and_members(parse)?;
+        Ok(Self {
+            union_tk,
+            tag,
+            members,
+        })
+    }
+}
+
+fn parse_tag_and_members(parse: ParseStream) -> Result<(Option<Ident>, Option<MemberList>)> {
+    let tag: Option<Ident> = parse.parse()?;
+    let members = if parse.peek::<tokens::Brace>() {
+        Some(parse.parse()?)
+    } else if tag.is_none() {
+        return Err(parse.error("expected tag or member list"));
+    } else {
+        None
+    };
+    Ok((tag, members))
+}
+
+impl Parse for MemberList {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let content;
+        let brace = braced!(content in parse)?;
+        let mut items = vec![];
+        while !content.is_empty() {
+            items.push(content.parse()?);
+        }
+        Ok(Self { brace, items })
+    }
+}
+
+impl Parse for Member {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        Ok(if parse.peek::<token![_Static_assert]>() {
+            Self::StaticAssert(parse.parse()?)
+        } else {
+            Self::Field(parse.parse()?)
+        })
+    }
+}
+
+impl Parse for MemberDeclr {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let specifiers = parse.parse()?;
+        let declarators = if parse.peek::<token![;]>() {
+            Punctuated::new()
+        } else {
+            Punctuated::parse_non_terminated(parse)?
+        };
+        Ok(Self {
+            specifiers,
+            declarators,
+            semi_colon: parse.parse()?,
+        })
+    }
+}
+
+impl Parse for MemberDeclarator {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let declarator = if parse.peek::<token![:]>() {
+            None
+        } else {
+            Some(parse.parse()?)
+        };
+        Ok(Self {
+            declarator,
+            bit_field: parse.parse()?,
+        })
+    }
+}
+
+impl Parse for BitField {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        Ok(Self {
+            colon: parse.parse()?,
+            width: Box::new(parse_assign(parse)?),
+        })
+    }
+}
+
+mod quote {
+    use super::*;
+    use crate::{to_tokens, ToTokens, TokenStream};
+
+    impl ToTokens for StructSpecifier {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                struct_tk,
+                tag,
+                members,
+            } = self;
+            struct_tk.to_tokens(tokens);
+            tag.to_tokens(tokens);
+            members.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for UnionSpecifier {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                union_tk,
+                tag,
+                members,
+            } = self;
+            union_tk.to_tokens(tokens);
+            tag.to_tokens(tokens);
+            members.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for MemberList {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { brace: _, items } = self;
+            to_tokens::braced(items).to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Member {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                Member::Field(e) => e.to_tokens(tokens),
+                Member::StaticAssert(e) => e.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for MemberDeclr {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                specifiers,
+                declarators,
+                semi_colon,
+            } = self;
+            specifiers.to_tokens(tokens);
+            declarators.to_tokens(tokens);
+            semi_colon.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for MemberDeclarator {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                declarator,
+                bit_field,
+            } = self;
+            declarator.to_tokens(tokens);
+            bit_field.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for BitField {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { colon, width } = self;
+            colon.to_tokens(tokens);
+            width.to_tokens(tokens);
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Target presets: the macros a compiler predefines for a target, and the
+//! sizes of its types.
+
+use crate::Dialect;
+
+/// Sizes in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeSizes {
+    pub short: usize,
+    pub int: usize,
+    pub long: usize,
+    pub long_long: usize,
+    pub pointer: usize,
+    pub float: usize,
+    pub double: usize,
+    pub long_double: usize,
+    pub wchar: usize,
+    /// Whether plain `char` is signed.
+    pub char_signed: bool,
+    pub little_endian: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Arch {
+    X86_64,
+    I686,
+    Aarch64,
+    Arm,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Os {
+    Linux,
+    Windows,
+    None,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetProfile {
+    triple: &'static str,
+    arch: Arch,
+    os: Os,
+    pub sizes: TypeSizes,
+}
+
+const LP64: TypeSizes = TypeSizes {
+    short: 2,
+    int: 4,
+    long: 8,
+    long_long: 8,
+    pointer: 8,
+    float: 4,
+    double: 8,
+    long_double: 16,
+    wchar: 4,
+    char_signed: true,
+    little_endian: true,
+};
+
+const ILP32: TypeSizes = TypeSizes {
+    long: 4,
+    pointer: 4,
+    long_double: 12,
+    ..LP64
+};
+
+impl TargetProfile {
+    pub fn x86_64_linux_gnu() -> Self {
+        Self {
+            triple: "x86_64-linux-gnu",
+            arch: Arch::X86_64,
+            os: Os::Linux,
+            sizes: LP64,
+        }
+    }
+
+    pub fn i686_linux_gnu() -> Self {
+        Self {
+            triple: "i686-linux-gnu",
+            arch: Arch::I686,
+            os: Os::Linux,
+            sizes: ILP32,
+        }
+    }
+
+    pub fn aarch64_linux_gnu() -> Self {
+        Self {
+            triple: "aarch64-linux-gnu",
+            arch: Arch::Aarch64,
+            os: Os::Linux,
+            sizes: TypeSizes {
+                char_signed: false,
+                ..LP64
+            },
+        }
+    }
+
+    pub fn arm_none_eabi() -> Self {
+        Self {
+            triple: "arm-none-eabi",
+            arch: Arch::Arm,
+            os: Os::None,
+            sizes: TypeSizes {
+                long_double: 8,
+                char_signed: false,
+                ..ILP32
+            },
+        }
+    }
+
+    pub fn x86_64_windows_msvc() -> Self {
+        Self {
+            triple: "x86_64-windows-msvc",
+            arch: Arch::X86_64,
+            os: Os::Windows,
+            sizes: TypeSizes {
+                long: 4,
+                long_double: 8,
+                wchar: 2,
+                ..LP64
+            },
+        }
+    }
+
+    pub fn from_triple(triple: &str) -> Option<Self> {
+        [
+            Self::x86_64_linux_gnu(),
+            Self::i686_linux_gnu(),
+            Self::aarch64_linux_gnu(),
+            Self::arm_none_eabi(),
+            Self::x86_64_windows_msvc(),
+        ]
+        .into_iter()
+        .find(|target| target.triple == triple)
+    }
+
+    pub fn triple(&self) -> &'static str {
+        self.triple
+    }
+
+    fn msvc(&self) -> bool {
+        self.os == Os::Windows
+    }
+
+    /// The macros the target's compiler predefines, as `(name, value)`.
+    /// Macros that intrude on the user's namespace (`linux`, `unix`, `i386`)
+    /// are only defined for [`Dialect::Gnu`].
+    pub fn predefined_macros(&self, dialect: Dialect) -> Vec<(String, String)> {
+        let mut macros = vec![];
+        let mut define = |name: &str, value: String| macros.push((name.to_string(), value));
+        let sizes = &self.sizes;
+        let bits = |bytes: usize| bytes * 8;
+        // maximum value of a signed integer type of `bytes` bytes
+        let max = |bytes: usize| ((1u128 << (bits(bytes) - 1)) - 1).to_string();
+        let (intmax_type, uintmax_type) = match sizes.long {
+            8 => ("long int", "long unsigned int"),
+            _ => ("long long int", "long long unsigned int"),
+        };
+        let (size_type, ptrdiff_type) = match (sizes.pointer, sizes.long) {
+            (4, _) => ("unsigned int", "int"),
+            (_, 8) => ("long unsigned int", "long int"),
+            _ => ("long long unsigned int", "long long int"),
+        };
+
+        define("__STDC__", "1".into());
+        define("__STDC_VERSION__", "201710L".into());
+        define("__STDC_HOSTED__", "1".into());
+        define("__CHAR_BIT__", "8".into());
+        for (name, size) in [
+            ("SHORT", sizes.short),
+            ("INT", sizes.int),
+            ("LONG", sizes.long),
+            ("LONG_LONG", sizes.long_long),
+            ("POINTER", sizes.pointer),
+            ("FLOAT", sizes.float),
+            ("DOUBLE", sizes.double),
+            ("LONG_DOUBLE", sizes.long_double),
+            ("WCHAR_T", sizes.wchar),
+            ("SIZE_T", sizes.pointer),
+            ("PTRDIFF_T", sizes.pointer),
+        ] {
+            define(&format!("__SIZEOF_{name}__"), size.to_string());
+        }
+        define("__SCHAR_MAX__", "0x7f".into());
+        define("__SHRT_MAX__", max(sizes.short));
+        define("__INT_MAX__", max(sizes.int));
+        define("__LONG_MAX__", format!("{}L", max(sizes.long)));
+        define("__LONG_LONG_MAX__", format!("{}LL", max(sizes.long_long)));
+        define("__SIZE_TYPE__", size_type.into());
+        define("__PTRDIFF_TYPE__", ptrdiff_type.into());
+        define("__INTMAX_TYPE__", intmax_type.into());
+        define("__UINTMAX_TYPE__", uintmax_type.into());
+        define("__INTPTR_TYPE__", ptrdiff_type.into());
+        define(
+            "__WCHAR_TYPE__",
+            match sizes.wchar {
+                2 => "short unsigned int",
+                _ if self.arch == Arch::Arm || self.arch == Arch::Aarch64 => "unsigned int",
+                _ => "int",
+            }
+            .into(),
+        );
+        if !sizes.char_signed {
+            define("__CHAR_UNSIGNED__", "1".into());
+        }
+        define("__ORDER_LITTLE_ENDIAN__", "1234".into());
+        define("__ORDER_BIG_ENDIAN__", "4321".into());
+        define(
+            "__BYTE_ORDER__",
+            if sizes.little_endian {
+                "__ORDER_LITTLE_ENDIAN__"
+            } else {
+                "__ORDER_BIG_ENDIAN__"
+            }
+            .into(),
+        );
+        if sizes.long == 8 && sizes.pointer == 8 {
+            define("__LP64__", "1".into());
+            define("_LP64", "1".into());
+        } else if sizes.pointer == 4 {
+            define("__ILP32__", "1".into());
+            define("_ILP32", "1".into());
+        }
+
+        let gnu = dialect == Dialect::Gnu;
+        match self.arch {
+            Arch::X86_64 if self.msvc() => {
+                define("_M_X64", "100".into());
+                define("_M_AMD64", "100".into());
+            }
+            Arch::X86_64 => {
+                for name in ["__x86_64__", "__x86_64", "__amd64__", "__amd64"] {
+                    define(name, "1".into());
+                }
+            }
+            Arch::I686 => {
+                for name in ["__i386__", "__i386", "__i686__", "__i686"] {
+                    define(name, "1".into());
+                }
+                if gnu {
+                    define("i386", "1".into());
+                }
+            }
+            Arch::Aarch64 => {
+                define("__aarch64__", "1".into());
+                define("__ARM_64BIT_STATE", "1".into());
+                define("__ARM_ARCH", "8".into());
+            }
+            Arch::Arm => {
+                define("__arm__", "1".into());
+                define("__ARM_32BIT_STATE", "1".into());
+                define("__ARM_EABI__", "1".into());
+                define("__ARMEL__", "1".into());
+                define("__ARM_ARCH", "7".into());
+            }
+        }
+        match self.os {
+            Os::Linux => {
+                for name in [
+                    "__linux__",
+                    "__linux",
+                    "__gnu_linux__",
+                    "__unix__",
+                    "__unix",
+                    "__ELF__",
+                ] {
+                    define(name, "1".into());
+                }
+                if gnu {
+                    define("linux", "1".into());
+                    define("unix", "1".into());
+                }
+            }
+            Os::Windows => {
+                define("_WIN32", "1".into());
+                define("_WIN64", "1".into());
+                define("_MSC_VER", "1930".into());
+                define("_INTEGRAL_MAX_BITS", "64".into());
+            }
+            Os::None => define("__ELF__", "1".into()),
+        }
+        if !self.msvc() {
+            define("__GNUC__", "13".into());
+            define("__GNUC_MINOR__", "2".into());
+            define("__GNUC_PATCHLEVEL__", "0".into());
+        }
+        macros
+    }
+}
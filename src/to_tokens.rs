@@ -28,6 +28,12 @@ impl<T: ToTokens> ToTokens for Box<T> {
     }
 }
 
+impl ToTokens for TokenStream {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self)
+    }
+}
+
 pub struct Delimited {
     tt: TokenTree,
 }
@@ -59,6 +65,12 @@ pub fn braced<T: ToTokens>(inner: &T) -> Delimited {
     delimited(inner, Delimeter::Brace)
 }
 
+pub fn directive<T: ToTokens>(inner: &T) -> Delimited {
+    Delimited {
+        tt: TokenTree::Directive(to_tokens(inner)),
+    }
+}
+
 pub struct Multiple {
     ts: TokenStream,
 }
@@ -30,33 +30,80 @@ pub(crate) fn match_punct(str: &str) -> PunctMatch {
     }
 }
 
+/// Numbers keep their source spelling next to the value, radix and suffix
+/// included, and are printed as written.
 #[derive(Clone, Debug)]
 pub enum Literal {
     Str(String),
-    Int(i128),
-    Float(f64),
+    /// `'a'`, escape sequences are kept as written.
+    Char(String),
+    Int(i128, String),
+    /// An integer with a `u` or `U` suffix.
+    UInt(i128, String),
+    Float(f64, String),
+    /// `<stdio.h>`, only lexed in `#include` directives.
+    Header(String),
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct TokenCell {
     pos: Option<Pos>,
+    close: Option<Pos>,
+    /// Printed without whitespace before the next token.
+    joint: bool,
+    /// Preceded by whitespace or a comment in the source.
+    space: bool,
     pub tt: TokenTree,
 }
 
 impl TokenCell {
     pub fn new(tt: TokenTree) -> Self {
-        Self { pos: None, tt }
+        Self {
+            pos: None,
+            close: None,
+            joint: false,
+            space: false,
+            tt,
+        }
     }
 
-    pub fn new_with_pos(tt: TokenTree, col: usize, row: usize) -> Self {
+    pub fn new_with_pos(tt: TokenTree, pos: Pos) -> Self {
         Self {
-            pos: Some(Pos { col, row }),
+            pos: Some(pos),
+            close: None,
+            joint: false,
+            space: false,
             tt,
         }
     }
 
+    pub fn joint(&self) -> bool {
+        self.joint
+    }
+
+    pub fn set_joint(&mut self) {
+        self.joint = true;
+    }
+
+    pub fn space(&self) -> bool {
+        self.space
+    }
+
+    pub fn set_space(&mut self, space: bool) {
+        self.space = space;
+    }
+
     pub fn pos(&self) -> Option<Pos> {
-        self.pos
+        self.pos.clone()
+    }
+
+    /// Position of the closing delimiter, only set for groups.
+    pub fn close(&self) -> Option<Pos> {
+        self.close.clone()
+    }
+
+    pub fn set_close(&mut self, close: Option<Pos>) {
+        self.close = close;
     }
 }
 
@@ -66,25 +113,45 @@ pub enum TokenTree {
     Literal(Literal),
     Punct(Punct),
     Group(Delimeter, TokenStream),
+    /// Only produced by [`TokenStream::from_str_with_comments`], the parser
+    /// never sees these.
+    Comment(String),
+    /// A preprocessing directive, holding the tokens following the `#` up to
+    /// the end of the line.
+    Directive(TokenStream),
 }
 
 impl Display for TokenTree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&match &self {
             TokenTree::Ident(ident) => ident.clone(),
-            TokenTree::Literal(Literal::Str(str)) => str.clone(),
-            TokenTree::Literal(Literal::Int(i)) => i.to_string(),
-            TokenTree::Literal(Literal::Float(f)) => f.to_string(),
+            TokenTree::Literal(Literal::Str(str)) => format!("\"{str}\""),
+            TokenTree::Literal(Literal::Char(char)) => format!("'{char}'"),
+            TokenTree::Literal(
+                Literal::Int(_, spelling)
+                | Literal::UInt(_, spelling)
+                | Literal::Float(_, spelling),
+            ) => spelling.clone(),
+            TokenTree::Literal(Literal::Header(header)) => format!("<{header}>"),
             TokenTree::Punct(punct) => punct.to_string(),
             TokenTree::Group(group, entries) => {
                 let string = entries.to_string();
                 let string = string.trim();
+                // keep a leading directive at the start of its own line
+                let string = match entries.cells().first() {
+                    Some(cell) if matches!(cell.tt, TokenTree::Directive(_)) => {
+                        format!("\n{string}")
+                    }
+                    _ => string.to_string(),
+                };
                 match group {
                     Delimeter::Paren => format!("( {string} )"),
                     Delimeter::Bracket => format!("[ {string} ]"),
                     Delimeter::Brace => format!("{{ {string} }}"),
                 }
             }
+            TokenTree::Comment(comment) => comment.clone(),
+            TokenTree::Directive(entries) => format!("#{}", entries.to_string().trim_end()),
         })
     }
 }
@@ -199,7 +266,7 @@ define_keywords!(
     "if"                pub struct If,
     "struct"            pub struct Struct,
     "_Bool"             pub struct _Bool,
-    "Continue"          pub struct Continue,
+    "continue"          pub struct Continue,
     "inline"            pub struct Inline,
     "switch"            pub struct Switch,
     "_Complex"          pub struct _Complex,
@@ -223,6 +290,8 @@ define_keywords!(
     "return"            pub struct Return,
     "volatile"          pub struct Volatile,
     "_Thread_local"     pub struct _Thread_local,
+    "alignas"           pub struct Alignas,
+    "alignof"           pub struct Alignof,
 );
 
 macro_rules! define_punctuator {
@@ -440,6 +509,8 @@ macro_rules! token {
     [return] => { $crate::tokens::Return };
     [volatile] => { $crate::tokens::Volatile };
     [_Thread_local] => { $crate::tokens::_Thread_local };
+    [alignas] => { $crate::tokens::Alignas };
+    [alignof] => { $crate::tokens::Alignof };
     [.] => { $crate::tokens::Dot };
     [->] => { $crate::tokens::Arrow };
     [++] => { $crate::tokens::PlusPlus };
@@ -1,9 +1,12 @@
-use crate::specifier::{SpecifierList, TypeQualifierList};
+use crate::declr::Derivation;
+use crate::specifier::{DeclSpecifiers, TypeQualifierList};
+use crate::{tokens, Expr, FnParams};
 
 ast_struct! {
-    pub struct Ty{
-        pub specifiers: SpecifierList,
-        pub ptr: Option<Ptr>,
+    /// A type without a declared name: `int (*)[10]`, `void (*)(int, char *)`.
+    pub struct TypeName {
+        pub specifiers: DeclSpecifiers,
+        pub declarator: Option<AbstractDeclarator>,
     }
 }
 
@@ -15,19 +18,113 @@ ast_struct! {
     }
 }
 
+ast_struct! {
+    /// At least one of `ptr` and `direct` is present.
+    pub struct AbstractDeclarator {
+        pub ptr: Option<Ptr>,
+        pub direct: Option<DirectAbstractDeclarator>,
+    }
+}
+
+ast_enum! {
+    pub enum DirectAbstractDeclarator {
+        Paren(DADParen),
+        Bracket(DADBracket),
+        Params(DADParams),
+    }
+}
+
+ast_struct! {
+    pub struct DADParen {
+        pub paren: tokens::Paren,
+        pub declarator: Box<AbstractDeclarator>,
+    }
+}
+
+ast_struct! {
+    pub struct DADBracket {
+        pub direct: Option<Box<DirectAbstractDeclarator>>,
+        pub bracket: tokens::Bracket,
+        pub static_tk: Option<token![static]>,
+        pub qualifier_list: Option<TypeQualifierList>,
+        pub star: Option<token![*]>,
+        pub assing_expr: Option<Box<Expr>>,
+    }
+}
+
+ast_struct! {
+    pub struct DADParams {
+        pub direct: Option<Box<DirectAbstractDeclarator>>,
+        pub params: FnParams,
+    }
+}
+
+impl TypeName {
+    /// The derivations applied to the specified type, in the same order as
+    /// [`Declarator::derivations`](crate::declr::Declarator::derivations).
+    pub fn derivations(&self) -> Vec<Derivation<'_>> {
+        let mut derivations = vec![];
+        if let Some(declarator) = &self.declarator {
+            declarator.push_derivations(&mut derivations);
+        }
+        derivations
+    }
+}
+
+impl AbstractDeclarator {
+    fn push_derivations<'a>(&'a self, derivations: &mut Vec<Derivation<'a>>) {
+        if let Some(direct) = &self.direct {
+            direct.push_derivations(derivations);
+        }
+        Derivation::push_pointers(self.ptr.as_ref(), derivations);
+    }
+}
+
+impl DirectAbstractDeclarator {
+    fn push_derivations<'a>(&'a self, derivations: &mut Vec<Derivation<'a>>) {
+        match self {
+            DirectAbstractDeclarator::Paren(paren) => {
+                paren.declarator.push_derivations(derivations)
+            }
+            DirectAbstractDeclarator::Bracket(bracket) => {
+                if let Some(direct) = &bracket.direct {
+                    direct.push_derivations(derivations);
+                }
+                derivations.push(Derivation::Array {
+                    is_static: bracket.static_tk.is_some(),
+                    qualifier_list: bracket.qualifier_list.as_ref(),
+                    is_star: bracket.star.is_some(),
+                    len: bracket.assing_expr.as_deref(),
+                });
+            }
+            DirectAbstractDeclarator::Params(params) => {
+                if let Some(direct) = &params.direct {
+                    direct.push_derivations(derivations);
+                }
+                derivations.push(Derivation::Function(&params.params));
+            }
+        }
+    }
+}
+
+use crate::declr::{parse_array_len, ArrayLen};
 use crate::{Parse, ParseStream, Result};
 
-impl Parse for Ty {
+impl Parse for TypeName {
     fn parse(parse: ParseStream) -> Result<Self> {
-        let specifiers = parse.parse::<SpecifierList>()?;
-        if specifiers.items.is_empty() {
-            Err(parse.error("empty specifier list"))
+        let specifiers = DeclSpecifiers::parse_type_name(parse)?;
+        let declarator = if parse.peek::<token![*]>()
+            || parse.peek::<tokens::Paren>()
+            || parse.peek::<tokens::Bracket>()
+        {
+            Some(parse.parse()?)
         } else {
-            Ok(Self {
-                ptr: parse.parse()?,
-                specifiers,
-            })
-        }
+            None
+        };
+        Ok(Self {
+            specifiers,
+            declarator,
+        })
     }
 }
 
@@ -51,17 +148,89 @@ impl Parse for Ptr {
     }
 }
 
+impl Parse for AbstractDeclarator {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let ptr: Option<Ptr> = parse.parse()?;
+        let direct = if parse.peek::<tokens::Paren>() || parse.peek::<tokens::Bracket>() {
+            Some(parse.parse()?)
+        } else if ptr.is_none() {
+            return Err(parse.error("expected abstract declarator"));
+        } else {
+            None
+        };
+        Ok(Self { ptr, direct })
+    }
+}
+
+/// Whether the parentheses ahead hold a nested abstract declarator, as in
+/// `int (*)[3]`, rather than the parameters of `int (int)`.
+fn peek_nested(parse: ParseStream) -> bool {
+    let fork = parse.fork();
+    let nested = || -> Result<bool> {
+        let content;
+        parenthesized!(content in &fork)?;
+        Ok(content.peek::<token![*]>()
+            || content.peek::<tokens::Paren>()
+            || content.peek::<tokens::Bracket>())
+    };
+    nested().unwrap_or(false)
+}
+
+impl Parse for DirectAbstractDeclarator {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let mut direct = if peek_nested(parse) {
+            let content;
+            let paren = parenthesized!(content in parse)?;
+            let declarator = content.parse()?;
+            if !content.is_empty() {
+                return Err(content.error("expected `)` after abstract declarator"));
+            }
+            Some(Self::Paren(DADParen { paren, declarator }))
+        } else {
+            None
+        };
+        loop {
+            if parse.peek::<tokens::Paren>() {
+                direct = Some(Self::Params(DADParams {
+                    direct: direct.map(Box::new),
+                    params: parse.parse()?,
+                }));
+            } else if parse.peek::<tokens::Bracket>() {
+                let content;
+                let bracket = bracketed!(content in parse)?;
+                let ArrayLen {
+                    static_tk,
+                    qualifier_list,
+                    star,
+                    len,
+                } = parse_array_len(&content)?;
+                direct = Some(Self::Bracket(DADBracket {
+                    direct: direct.map(Box::new),
+                    bracket,
+                    static_tk,
+                    qualifier_list,
+                    star,
+                    assing_expr: len.map(Box::new),
+                }));
+            } else {
+                break direct.ok_or_else(|| parse.error("expected abstract declarator"));
+            }
+        }
+    }
+}
+
 mod quote {
-    use super::{Ptr, Ty};
-    use crate::{ToTokens, TokenStream};
+    use super::*;
+    use crate::{to_tokens, ToTokens, TokenStream};
 
-    impl ToTokens for Ty {
+    impl ToTokens for TypeName {
         fn to_tokens(&self, tokens: &mut TokenStream) {
-            let Self { specifiers, ptr } = self;
+            let Self {
+                specifiers,
+                declarator,
+            } = self;
             specifiers.to_tokens(tokens);
-            if let Some(ptr) = ptr {
-                ptr.to_tokens(tokens);
-            }
+            declarator.to_tokens(tokens);
         }
     }
 
@@ -77,4 +246,61 @@ mod quote {
             next.to_tokens(tokens);
         }
     }
+
+    impl ToTokens for AbstractDeclarator {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { ptr, direct } = self;
+            ptr.to_tokens(tokens);
+            direct.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for DirectAbstractDeclarator {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                DirectAbstractDeclarator::Paren(e) => e.to_tokens(tokens),
+                DirectAbstractDeclarator::Bracket(e) => e.to_tokens(tokens),
+                DirectAbstractDeclarator::Params(e) => e.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for DADParen {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                paren: _,
+                declarator,
+            } = self;
+            to_tokens::parenthesized(declarator).to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for DADBracket {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self {
+                direct,
+                bracket: _,
+                static_tk,
+                qualifier_list,
+                star,
+                assing_expr,
+            } = self;
+            direct.to_tokens(tokens);
+            to_tokens::bracketed(&to_tokens::multiple(|tokens| {
+                static_tk.to_tokens(tokens);
+                qualifier_list.to_tokens(tokens);
+                star.to_tokens(tokens);
+                assing_expr.to_tokens(tokens);
+            }))
+            .to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for DADParams {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            let Self { direct, params } = self;
+            direct.to_tokens(tokens);
+            params.to_tokens(tokens);
+        }
+    }
 }
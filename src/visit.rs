@@ -0,0 +1,123 @@
+//! Read-only traversal of expressions. Each method defaults to the free
+//! function of the same name, which visits the node's children; override a
+//! method to look at a node and call the free function to keep descending.
+
+use crate::expr::{ExprGeneric, GenericAssociation, GenericSelector};
+use crate::initializer::{Designator, Initializer, InitializerList};
+use crate::{Expr, TypeName};
+
+pub trait Visit<'ast> {
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        visit_expr(self, node)
+    }
+
+    fn visit_expr_generic(&mut self, node: &'ast ExprGeneric) {
+        visit_expr_generic(self, node)
+    }
+
+    fn visit_generic_association(&mut self, node: &'ast GenericAssociation) {
+        visit_generic_association(self, node)
+    }
+
+    fn visit_initializer(&mut self, node: &'ast Initializer) {
+        visit_initializer(self, node)
+    }
+
+    fn visit_initializer_list(&mut self, node: &'ast InitializerList) {
+        visit_initializer_list(self, node)
+    }
+
+    /// A leaf, type names aren't descended into.
+    fn visit_type_name(&mut self, _node: &'ast TypeName) {}
+}
+
+pub fn visit_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Expr) {
+    match node {
+        Expr::Ident(_) | Expr::Lit(_) => {}
+        Expr::Unary(e) => v.visit_expr(&e.expr),
+        Expr::Cast(e) => {
+            v.visit_type_name(&e.ty);
+            v.visit_expr(&e.expr);
+        }
+        Expr::Binary(e) => {
+            v.visit_expr(&e.lhs);
+            v.visit_expr(&e.rhs);
+        }
+        Expr::Paren(e) => v.visit_expr(&e.expr),
+        Expr::Assing(e) => {
+            v.visit_expr(&e.lhs);
+            v.visit_expr(&e.rhs);
+        }
+        Expr::Subscript(e) => {
+            v.visit_expr(&e.body);
+            v.visit_expr(&e.index);
+        }
+        Expr::FnCall(e) => {
+            v.visit_expr(&e.body);
+            e.args.args.iter().for_each(|arg| v.visit_expr(arg));
+        }
+        Expr::Member(e) => v.visit_expr(&e.body),
+        Expr::MemberPtr(e) => v.visit_expr(&e.body),
+        Expr::Postfix(e) => v.visit_expr(&e.expr),
+        Expr::Initialize(e) => {
+            v.visit_type_name(&e.ty);
+            v.visit_initializer_list(&e.init_list);
+        }
+        Expr::Sizeof(e) => v.visit_expr(&e.expr),
+        Expr::SizeofType(e) => v.visit_type_name(&e.ty),
+        Expr::Alignof(e) => v.visit_type_name(&e.ty),
+        Expr::Ternary(e) => {
+            v.visit_expr(&e.cond);
+            v.visit_expr(&e.true_expr);
+            v.visit_expr(&e.false_expr);
+        }
+        Expr::Comma(e) => {
+            v.visit_expr(&e.left);
+            v.visit_expr(&e.right);
+        }
+        Expr::Generic(e) => v.visit_expr_generic(e),
+    }
+}
+
+pub fn visit_expr_generic<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprGeneric) {
+    v.visit_expr(&node.expr);
+    for association in node.associations.iter() {
+        v.visit_generic_association(association);
+    }
+}
+
+pub fn visit_generic_association<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast GenericAssociation,
+) {
+    if let GenericSelector::Type(ty) = &node.selector {
+        v.visit_type_name(ty);
+    }
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_initializer<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Initializer) {
+    match node {
+        Initializer::Expr(expr) => v.visit_expr(expr),
+        Initializer::List(list) => v.visit_initializer_list(list),
+    }
+}
+
+pub fn visit_initializer_list<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast InitializerList,
+) {
+    for item in node.items.iter() {
+        for designator in item.designators() {
+            match designator {
+                Designator::Field(_) => {}
+                Designator::Index(index) => v.visit_expr(&index.index),
+                Designator::Range(range) => {
+                    v.visit_expr(&range.start);
+                    v.visit_expr(&range.end);
+                }
+            }
+        }
+        v.visit_initializer(&item.initializer);
+    }
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::specifier::{
+    AlignasKeyword, AlignmentSpecifier, DeclSpecifiers, Specifier, TypeSpecifier,
+};
+use cyn::struct_union::Member;
+use cyn::{to_tokens, Declr, Expr, Item};
+
+const INPUT: &str = r#"typedef double real;
+_Alignas(16) char buf[64];
+static alignas(real) int x;
+_Alignas(double) _Alignas(2 * 8) long y;
+struct s {
+    _Alignas(long) char c;
+};
+"#;
+
+fn specifiers(item: &Item) -> &DeclSpecifiers {
+    match item {
+        Item::Declr(Declr { specifiers, .. }) => specifiers,
+        _ => panic!("expected declaration"),
+    }
+}
+
+fn alignments(item: &Item) -> Vec<&AlignmentSpecifier> {
+    specifiers(item).alignments().collect()
+}
+
+#[test]
+fn declarations() {
+    let file = parse(INPUT);
+
+    let buf = alignments(&file.declarations[1]);
+    assert_eq!(buf.len(), 1);
+    assert!(matches!(buf[0].keyword, AlignasKeyword::Alignas(_)));
+    assert!(matches!(buf[0].expr(), Some(Expr::Lit(_))));
+    assert!(buf[0].type_name().is_none());
+
+    let x = alignments(&file.declarations[2]);
+    assert!(matches!(x[0].keyword, AlignasKeyword::C23(_)));
+    let ty = x[0].type_name().unwrap();
+    assert!(matches!(
+        ty.specifiers.items[..],
+        [Specifier::Type(TypeSpecifier::TypedefName(_))]
+    ));
+
+    let y = alignments(&file.declarations[3]);
+    assert_eq!(y.len(), 2);
+    assert!(y[0].type_name().is_some());
+    assert!(matches!(y[1].expr(), Some(Expr::Binary(_))));
+}
+
+#[test]
+fn members() {
+    let file = parse(INPUT);
+    let Specifier::Type(TypeSpecifier::Struct(s)) = &specifiers(&file.declarations[4]).items[0]
+    else {
+        panic!("expected struct");
+    };
+    let Member::Field(c) = &s.members.as_ref().unwrap().items[0] else {
+        panic!("expected field");
+    };
+    assert_eq!(c.specifiers.alignments().count(), 1);
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = file.declarations[1..4]
+        .iter()
+        .map(|item| {
+            to_tokens::to_tokens(specifiers(item))
+                .to_string()
+                .trim()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "_Alignas ( 16 ) char",
+            "static alignas ( real ) int",
+            "_Alignas ( double ) _Alignas ( 2 * 8 ) long"
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        parse_err("_Alignas() int x;").0,
+        "expected primary expression."
+    );
+    let (msg, pos) = parse_err("_Alignas(int 4) int x;");
+    assert_eq!(msg, "expected `)` after alignment");
+    assert_eq!(pos.unwrap().col, 14);
+    let (msg, pos) = parse_err("alignas int x;");
+    assert_eq!(msg, "expected (");
+    assert_eq!(pos.unwrap().col, 9);
+}
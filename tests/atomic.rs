@@ -0,0 +1,93 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::specifier::{DeclSpecifiers, Specifier, TypeQualifier, TypeSpecifier};
+use cyn::{to_tokens, Declr, Item};
+
+const INPUT: &str = r#"typedef struct node node;
+_Atomic(struct node *) head;
+_Atomic int counter;
+static _Atomic(node *) volatile tail;
+int *_Atomic slot;
+int plain;
+"#;
+
+fn specifiers(item: &Item) -> &DeclSpecifiers {
+    match item {
+        Item::Declr(Declr { specifiers, .. }) => specifiers,
+        _ => panic!("expected declaration"),
+    }
+}
+
+#[test]
+fn forms() {
+    let file = parse(INPUT);
+
+    let head = specifiers(&file.declarations[1]);
+    let [Specifier::Type(TypeSpecifier::Atomic(atomic))] = &head.items[..] else {
+        panic!("expected _Atomic specifier");
+    };
+    assert_eq!(atomic.ty.derivations().len(), 1);
+    assert!(head.is_atomic());
+
+    let counter = specifiers(&file.declarations[2]);
+    assert!(matches!(
+        counter.items[..],
+        [
+            Specifier::Qualifier(TypeQualifier::Atomic(_)),
+            Specifier::Type(TypeSpecifier::Int(_))
+        ]
+    ));
+    assert!(counter.is_atomic());
+
+    let tail = specifiers(&file.declarations[3]);
+    assert!(matches!(
+        tail.items[1],
+        Specifier::Type(TypeSpecifier::Atomic(_))
+    ));
+    let Item::Declr(tail) = &file.declarations[3] else {
+        unreachable!();
+    };
+    let tail = tail.vars.iter().next().unwrap().declarator();
+    assert_eq!(tail.ident().as_str(), "tail");
+    assert!(tail.derivations().is_empty());
+
+    assert!(!specifiers(&file.declarations[4]).is_atomic());
+    assert!(!specifiers(&file.declarations[5]).is_atomic());
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = file.declarations[1..5]
+        .iter()
+        .map(|item| {
+            let printed = to_tokens::to_tokens(item).to_string();
+            printed.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "_Atomic ( struct node * ) head ;",
+            "_Atomic int counter ;",
+            "static _Atomic ( node * ) volatile tail ;",
+            "int * _Atomic slot ;",
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        ("_Atomic(int x) y;", 13),
+        ("void f(void) { _Atomic(int x) y; }", 28),
+        ("struct s { _Atomic(int x) y; };", 24),
+        ("void f(_Atomic(int x) y);", 20),
+    ];
+    for (input, col) in cases {
+        let (err, pos) = parse_err(input);
+        assert_eq!(err, "expected `)` after type name", "{input}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((1, col)), "{input}");
+    }
+}
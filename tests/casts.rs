@@ -0,0 +1,123 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::op::{BiOp, UnOp};
+use cyn::stmnt::StmntExpr;
+use cyn::{Expr, Item, Stmnt};
+
+fn exprs(body: &str) -> Vec<Expr> {
+    let input = format!("typedef int T;\nvoid g(void) {{\n{body}\n}}\n");
+    let file = parse(&input);
+    let Some(Item::Fn(g)) = file.declarations.into_iter().nth(1) else {
+        panic!("expected function");
+    };
+    g.body
+        .items
+        .into_iter()
+        .map(|item| match item {
+            BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => *expr,
+            _ => panic!("expected expression statement"),
+        })
+        .collect()
+}
+
+fn cast_operand(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Cast(cast) => &cast.expr,
+        _ => panic!("expected cast"),
+    }
+}
+
+fn binary(expr: &Expr) -> (&Expr, &BiOp) {
+    match expr {
+        Expr::Binary(binary) => (&binary.lhs, &binary.op),
+        _ => panic!("expected binary expression"),
+    }
+}
+
+#[test]
+fn unary_or_binary() {
+    let exprs = exprs("(T)*p;\n(a)*b;\n(T)-1;\n(a)-1;\n(T)&a;\n(a)&b;\n(T)+x;\n(a)+x;");
+    for (i, op) in [0, 2, 4, 6].into_iter().zip(["*", "-", "&", "+"]) {
+        let Expr::Unary(unary) = cast_operand(&exprs[i]) else {
+            panic!("expected unary operand of `(T){op}`");
+        };
+        let matches = match &unary.op {
+            UnOp::Deref(_) => op == "*",
+            UnOp::Neg(_) => op == "-",
+            UnOp::Addr(_) => op == "&",
+            UnOp::Promote(_) => op == "+",
+            _ => false,
+        };
+        assert!(matches, "`(T){op}`");
+    }
+    for i in [1, 3, 5, 7] {
+        let (lhs, _) = binary(&exprs[i]);
+        assert!(matches!(lhs, Expr::Paren(_)));
+    }
+}
+
+#[test]
+fn parenthesized_expressions() {
+    let exprs = exprs("(x);\n(f)(y);\n(a)[1];\n(a)++;\n((T)x);");
+    assert!(matches!(exprs[0], Expr::Paren(_)));
+    assert!(matches!(&exprs[1], Expr::FnCall(call) if matches!(*call.body, Expr::Paren(_))));
+    assert!(matches!(exprs[2], Expr::Subscript(_)));
+    assert!(matches!(exprs[3], Expr::Postfix(_)));
+    let Expr::Paren(paren) = &exprs[4] else {
+        panic!("expected parenthesized cast");
+    };
+    assert!(matches!(*paren.expr, Expr::Cast(_)));
+}
+
+#[test]
+fn casts_and_literals() {
+    let exprs = exprs("(T)(x);\n(unsigned long)(T)*p;\n(int)(x) + 1;\n(T){1};\n(T)(*f)(x);");
+    assert!(matches!(cast_operand(&exprs[0]), Expr::Paren(_)));
+    assert!(matches!(
+        cast_operand(cast_operand(&exprs[1])),
+        Expr::Unary(_)
+    ));
+    let (lhs, op) = binary(&exprs[2]);
+    assert!(matches!(op, BiOp::Add(_)));
+    assert!(matches!(lhs, Expr::Cast(_)));
+    assert!(matches!(exprs[3], Expr::Initialize(_)));
+    assert!(matches!(cast_operand(&exprs[4]), Expr::FnCall(_)));
+}
+
+#[test]
+fn shadowed_typedef() {
+    let input = "typedef int T;\nvoid g(int p) {\n    int T = 2;\n    (T)*p;\n    (T)-1;\n}\n";
+    let file = parse(input);
+    let Item::Fn(g) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    for item in &g.body.items[1..] {
+        let BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) = item else {
+            panic!("expected expression statement");
+        };
+        let (lhs, _) = binary(expr);
+        assert!(matches!(lhs, Expr::Paren(_)));
+    }
+}
+
+#[test]
+fn errors() {
+    let primary = "expected primary expression.";
+    let argument = "expected `,` or `)` after argument";
+    let cases = [
+        ("(T);", primary, 4),
+        ("(int);", primary, 6),
+        ("(T x)1;", "expected `)` after type name", 4),
+        ("x = (a b);", "expected `)`", 8),
+        ("a[1 2];", "expected `]`", 5),
+        ("f(a b);", argument, 5),
+        ("f(a, b c);", argument, 8),
+    ];
+    for (body, msg, col) in cases {
+        let (err, pos) = parse_err(&format!("typedef int T;\nvoid g(void) {{\n{body}\n}}\n"));
+        assert_eq!(err, msg, "{body}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((3, col)), "{body}");
+    }
+}
@@ -0,0 +1,33 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use cyn::error::Pos;
+use cyn::file::File;
+use cyn::{ParseConfig, TokenStream};
+
+/// Parses `input` as a file, panicking with the error message.
+pub fn parse(input: &str) -> File {
+    parse_with(input, ParseConfig::default())
+}
+
+pub fn parse_with(input: &str, config: ParseConfig) -> File {
+    let ts = TokenStream::from_str(input).unwrap();
+    match ts.parse_with_config::<File>(config) {
+        Ok(file) => file,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Parses `input` as a file, panicking if it succeeds. Returns the error
+/// message and its position, if any.
+pub fn parse_err(input: &str) -> (String, Option<Pos>) {
+    parse_err_with(input, ParseConfig::default())
+}
+
+pub fn parse_err_with(input: &str, config: ParseConfig) -> (String, Option<Pos>) {
+    let ts = TokenStream::from_str(input).unwrap();
+    match ts.parse_with_config::<File>(config) {
+        Ok(_) => panic!("expected an error for {input:?}"),
+        Err(err) => (err.msg().clone(), err.pos()),
+    }
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::file::File;
+use cyn::initializer::Initializer;
+use cyn::stmnt::StmntExpr;
+use cyn::{to_tokens, Expr, Item, Stmnt};
+
+const INPUT: &str = r#"typedef struct point { int x, y; } point;
+void f(int n) {
+    p = (struct point){ .x = 1, .y = 2 };
+    sum((int[]){1, 2, 3});
+    n = (point){ 4, 5 }.y;
+    q = &(point){0};
+    n = (long)n;
+}
+"#;
+
+fn exprs(file: &File) -> Vec<&Expr> {
+    let Item::Fn(f) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    f.body
+        .items
+        .iter()
+        .map(|item| match item {
+            BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => &**expr,
+            _ => panic!("expected expression statement"),
+        })
+        .collect()
+}
+
+fn rhs(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Assing(assign) => &assign.rhs,
+        _ => panic!("expected assignment"),
+    }
+}
+
+#[test]
+fn literals() {
+    let file = parse(INPUT);
+    let exprs = exprs(&file);
+
+    let Expr::Initialize(point) = rhs(exprs[0]) else {
+        panic!("expected compound literal");
+    };
+    assert_eq!(point.init_list.items.len(), 2);
+
+    let Expr::FnCall(call) = exprs[1] else {
+        panic!("expected call");
+    };
+    let Expr::Initialize(array) = call.args.args.iter().next().unwrap() else {
+        panic!("expected compound literal");
+    };
+    assert_eq!(array.ty.derivations().len(), 1);
+    assert!(matches!(
+        array.init_list.items.iter().next().unwrap().initializer,
+        Initializer::Expr(_)
+    ));
+
+    let Expr::Member(member) = rhs(exprs[2]) else {
+        panic!("expected member access");
+    };
+    assert!(matches!(*member.body, Expr::Initialize(_)));
+
+    let Expr::Unary(addr) = rhs(exprs[3]) else {
+        panic!("expected address-of");
+    };
+    assert!(matches!(*addr.expr, Expr::Initialize(_)));
+
+    assert!(matches!(rhs(exprs[4]), Expr::Cast(_)));
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let exprs = exprs(&file);
+    let printed = to_tokens::to_tokens(rhs(exprs[2])).to_string();
+    let printed = printed.split_whitespace().collect::<String>();
+    assert_eq!(printed, "(point){4,5}.y");
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        (
+            "void f(void) { p = (int x){1}; }",
+            "expected `)` after type name",
+            25,
+        ),
+        (
+            "void f(void) { p = (int[]){1, 2}.; }",
+            "expected identifier",
+            34,
+        ),
+    ];
+    for (input, msg, col) in cases {
+        let (err, pos) = parse_err(input);
+        assert_eq!(err, msg, "{input}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((1, col)), "{input}");
+    }
+}
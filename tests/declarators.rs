@@ -0,0 +1,85 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::declr::{Derivation, InitDeclarator};
+use cyn::{to_tokens, Declr, FnParam, Item};
+
+fn shape(derivations: Vec<Derivation>) -> String {
+    derivations
+        .iter()
+        .map(|derivation| match derivation {
+            Derivation::Pointer(None) => "*",
+            Derivation::Pointer(Some(_)) => "*q",
+            Derivation::Array { .. } => "[]",
+            Derivation::Function(_) | Derivation::IdentList(_) => "()",
+        })
+        .collect()
+}
+
+fn vars(declr: &Declr) -> Vec<(&str, String)> {
+    declr
+        .vars
+        .iter()
+        .map(InitDeclarator::declarator)
+        .map(|declarator| (declarator.ident().as_str(), shape(declarator.derivations())))
+        .collect()
+}
+
+#[test]
+fn per_declarator_derivations() {
+    let file = parse("void g(void) {\n    int *a, b, (*c)[3], *d[3], * const *e, (*f)(int);\n}\n");
+    let Item::Fn(g) = &file.declarations[0] else {
+        panic!("expected function");
+    };
+    let BlockItem::Declr(declr) = &g.body.items[0] else {
+        panic!("expected declaration");
+    };
+    assert_eq!(declr.specifiers.items.len(), 1);
+    assert_eq!(
+        vars(declr),
+        [
+            ("a", "*".to_string()),
+            ("b", String::new()),
+            ("c", "*[]".to_string()),
+            ("d", "[]*".to_string()),
+            ("e", "**q".to_string()),
+            ("f", "*()".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn signatures_and_parameters() {
+    let file =
+        parse("char *copy(char *dst, const char *, int (*cmp)(int)) {\n    return dst;\n}\n");
+    let Item::Fn(f) = &file.declarations[0] else {
+        panic!("expected function");
+    };
+    assert_eq!(f.ident().as_str(), "copy");
+    assert_eq!(shape(f.declarator.derivations()), "()*");
+    let params: Vec<_> = f.params().unwrap().params.iter().collect();
+    match params[0] {
+        FnParam::Named(named) => assert_eq!(named.declarator.ident().as_str(), "dst"),
+        FnParam::Unnamed(_) => panic!("expected named parameter"),
+    }
+    assert!(matches!(params[1], FnParam::Unnamed(_)));
+    match params[2] {
+        FnParam::Named(named) => assert_eq!(shape(named.declarator.derivations()), "*()"),
+        FnParam::Unnamed(_) => panic!("expected named parameter"),
+    }
+    let printed = to_tokens::to_tokens(&f.declarator).to_string();
+    assert_eq!(
+        printed.trim(),
+        "* copy ( char * dst , const char * , int ( * cmp ) ( int ) )"
+    );
+}
+
+#[test]
+fn errors() {
+    for (input, col) in [("int (x y);", 8), ("void f(void) { int (*p y)[3]; }", 24)] {
+        let (msg, pos) = parse_err(input);
+        assert_eq!(msg, "expected `)` after declarator");
+        assert_eq!(pos.unwrap().col, col);
+    }
+}
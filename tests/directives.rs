@@ -0,0 +1,172 @@
+mod common;
+
+use common::{parse_err, parse_err_with};
+use cyn::block::BlockItem;
+use cyn::directive::{ConditionalKind, IncludePath};
+use cyn::{file::File, to_tokens, Item, ParseConfig};
+
+const INPUT: &str = r#"#include <stdio.h>
+#include "foo.h"
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define EMPTY
+#define PAREN (x)
+
+#ifdef _WIN32
+int win;
+#elif defined(__linux__)
+int linux_;
+int linux2;
+#else
+int other;
+#endif
+
+int f(int x) {
+#if DEBUG
+    x = 1;
+#endif
+    return x;
+}
+#undef MAX
+"#;
+
+fn parse(input: &str) -> File {
+    common::parse_with(input, preserving())
+}
+
+fn preserving() -> ParseConfig {
+    ParseConfig {
+        preserve_directives: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn directives_as_items() {
+    let file = parse(INPUT);
+    let items = &file.declarations;
+    assert_eq!(items.len(), 8);
+    match &items[0] {
+        Item::Include(include) => {
+            assert!(matches!(&include.path, IncludePath::Angled(path) if path == "stdio.h"))
+        }
+        _ => panic!("expected include"),
+    }
+    match &items[1] {
+        Item::Include(include) => {
+            assert!(matches!(&include.path, IncludePath::Quoted(path) if path == "foo.h"))
+        }
+        _ => panic!("expected include"),
+    }
+    match &items[2] {
+        Item::Define(define) => {
+            assert_eq!(define.name.as_str(), "MAX");
+            assert_eq!(define.params.as_ref().unwrap().params.len(), 2);
+        }
+        _ => panic!("expected define"),
+    }
+    match &items[4] {
+        Item::Define(define) => {
+            assert_eq!(define.name.as_str(), "PAREN");
+            assert!(define.params.is_none());
+            assert_eq!(define.body.to_string().trim(), "( x )");
+        }
+        _ => panic!("expected define"),
+    }
+    match &items[5] {
+        Item::Conditional(cond) => {
+            let kinds: Vec<_> = cond.branches.iter().map(|b| b.kind).collect();
+            assert_eq!(
+                kinds,
+                [
+                    ConditionalKind::Ifdef,
+                    ConditionalKind::Elif,
+                    ConditionalKind::Else
+                ]
+            );
+            let lens: Vec<_> = cond.branches.iter().map(|b| b.items.len()).collect();
+            assert_eq!(lens, [1, 2, 1]);
+        }
+        _ => panic!("expected conditional"),
+    }
+    match &items[6] {
+        Item::Fn(f) => assert!(matches!(f.body.items[0], BlockItem::Conditional(_))),
+        _ => panic!("expected function"),
+    }
+    match &items[7] {
+        Item::Directive(directive) => {
+            assert_eq!(directive.name.as_ref().unwrap().as_str(), "undef")
+        }
+        _ => panic!("expected directive"),
+    }
+}
+
+#[test]
+fn round_trip() {
+    let printed = to_tokens::to_tokens(&parse(INPUT)).to_string();
+    let lines: Vec<_> = printed.lines().map(str::trim).collect();
+    assert!(lines.contains(&"#include <stdio.h>"));
+    assert!(lines.contains(&"#include \"foo.h\""));
+    assert!(lines.contains(&"#define MAX( a , b ) ( ( a ) > ( b ) ? ( a ) : ( b ) )"));
+    assert!(lines.contains(&"#define PAREN ( x )"));
+    assert!(lines.contains(&"#elif defined ( __linux__ )"));
+    assert!(lines.contains(&"#endif"));
+    let reprinted = to_tokens::to_tokens(&parse(&printed)).to_string();
+    assert_eq!(printed, reprinted);
+}
+
+#[test]
+fn directives_rejected_by_default() {
+    let (msg, pos) = parse_err(INPUT);
+    assert_eq!(msg, "expected at least one specifier");
+    assert_eq!(pos.unwrap().row, 1);
+}
+
+#[test]
+fn partial_conditional() {
+    let (msg, _) = parse_err_with("#ifdef A\nint a;\n", preserving());
+    assert_eq!(msg, "unterminated #ifdef");
+}
+
+#[test]
+fn line_splices() {
+    let file = parse("#define ADD(a, b) \\\n    ((a) + \\\n     (b))\nint x;\n");
+    match &file.declarations[0] {
+        Item::Define(define) => assert_eq!(define.body.to_string().trim(), "( ( a ) + ( b ) )"),
+        _ => panic!("expected define"),
+    }
+    assert_eq!(file.declarations.len(), 2);
+}
+
+#[test]
+fn variadic_macros() {
+    let input = "#define LOG(fmt, ...) printf(fmt, __VA_ARGS__)\n\
+                 #define ANY(...) f(__VA_ARGS__)\n\
+                 #define GNU(fmt, args...) printf(fmt, args)\n\
+                 int x;\n";
+    let file = parse(input);
+    let params: Vec<_> = file.declarations[..3]
+        .iter()
+        .map(|item| match item {
+            Item::Define(define) => define.params.as_ref().unwrap(),
+            _ => panic!("expected define"),
+        })
+        .collect();
+    assert_eq!(params[0].params.len(), 1);
+    assert!(params[0].variadic.is_some() && params[0].variadic_name.is_none());
+    assert!(params[1].params.is_empty() && params[1].variadic.is_some());
+    assert_eq!(params[2].params.len(), 1);
+    assert_eq!(params[2].variadic_name.as_ref().unwrap().as_str(), "args");
+
+    let printed = to_tokens::to_tokens(&file).to_string();
+    let lines: Vec<_> = printed.lines().map(str::trim).collect();
+    assert!(lines.contains(&"#define LOG( fmt , ... ) printf ( fmt , __VA_ARGS__ )"));
+    assert!(lines.contains(&"#define ANY( ... ) f ( __VA_ARGS__ )"));
+    assert!(lines.contains(&"#define GNU( fmt , args ... ) printf ( fmt , args )"));
+    assert_eq!(to_tokens::to_tokens(&parse(&printed)).to_string(), printed);
+
+    for (input, col) in [("#define F(..., a) a\n", 14), ("#define F(a b) a\n", 13)] {
+        let (msg, pos) = parse_err_with(input, preserving());
+        assert_eq!(msg, "expected `,` or `)` after macro parameter");
+        assert_eq!(pos.unwrap().col, col);
+    }
+}
@@ -0,0 +1,70 @@
+use cyn::emit::{emit, EmitOptions};
+use cyn::pp::Preprocessor;
+use cyn::TokenStream;
+
+fn emit_str(input: &str, options: &EmitOptions) -> String {
+    let ts = match TokenStream::from_str_with_comments(input) {
+        Ok(ts) => ts,
+        Err(err) => panic!("{err}"),
+    };
+    emit(&ts, options)
+}
+
+#[test]
+fn keeps_lines_aligned() {
+    let input = "int a;\n\nint f(int x) {\n    return x+1;\n}\n";
+    let out = emit_str(input, &EmitOptions::default());
+    assert_eq!(
+        out,
+        "# 1 \"<stdin>\"\nint a;\n\nint f(int x) {\n    return x+1;\n}\n"
+    );
+}
+
+#[test]
+fn line_marker_after_large_gap() {
+    let input = format!("int a;{}int b;\n", "\n".repeat(12));
+    let out = emit_str(&input, &EmitOptions::default());
+    assert_eq!(out, "# 1 \"<stdin>\"\nint a;\n# 13 \"<stdin>\"\nint b;\n");
+
+    let options = EmitOptions {
+        line_markers: false,
+        ..Default::default()
+    };
+    let out = emit_str(&input, &options);
+    assert_eq!(out, format!("int a;{}int b;\n", "\n".repeat(12)));
+}
+
+#[test]
+fn comments() {
+    let input = "int a; // first\n/* multi\n   line */ int b;\n";
+    let out = emit_str(input, &EmitOptions::default());
+    assert_eq!(out, "# 1 \"<stdin>\"\nint a;\n\n           int b;\n");
+
+    let options = EmitOptions {
+        keep_comments: true,
+        ..Default::default()
+    };
+    let out = emit_str(input, &options);
+    assert_eq!(
+        out,
+        "# 1 \"<stdin>\"\nint a; // first\n/* multi\n   line */ int b;\n"
+    );
+}
+
+#[test]
+fn literals_keep_their_spelling() {
+    let input = "int a = 0x10 + 1.5f + 1e3 +2;\nunsigned long long b = 10ULL<<3LL;\nchar c = '\\n' + 'a';\n";
+    let out = emit_str(input, &EmitOptions::default());
+    assert_eq!(out, format!("# 1 \"<stdin>\"\n{input}"));
+}
+
+#[test]
+fn pragma_operator_mid_line() {
+    let input = "x _Pragma(\"once\") y\nz;\n";
+    let ts = Preprocessor::default().preprocess(input).unwrap();
+    let out = emit(&ts, &EmitOptions::default());
+    assert_eq!(
+        out,
+        "# 1 \"<stdin>\"\nx\n#pragma once\n# 1 \"<stdin>\"\n                  y\nz;\n"
+    );
+}
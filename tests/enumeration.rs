@@ -0,0 +1,76 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::enumeration::EnumSpecifier;
+use cyn::specifier::{Specifier, TypeSpecifier};
+use cyn::{to_tokens, Declr, Expr, Item, Stmnt, ToTokens};
+
+const INPUT: &str = r#"enum color { RED, GREEN = 2, BLUE = GREEN << 1, };
+enum color c;
+int is_green(int c) {
+    return c == GREEN;
+}
+"#;
+
+fn enumeration(item: &Item) -> &EnumSpecifier {
+    let Item::Declr(Declr { specifiers, .. }) = item else {
+        panic!("expected declaration");
+    };
+    match &specifiers.items[0] {
+        Specifier::Type(TypeSpecifier::Enum(enumeration)) => enumeration,
+        _ => panic!("expected enum"),
+    }
+}
+
+fn print(node: &impl ToTokens) -> String {
+    to_tokens::to_tokens(node).to_string().trim().to_string()
+}
+
+#[test]
+fn enumerators() {
+    let file = parse(INPUT);
+    let color = enumeration(&file.declarations[0]);
+    assert_eq!(color.tag.as_ref().unwrap().as_str(), "color");
+    let enumerators: Vec<_> = color
+        .enumerators()
+        .map(|(ident, value)| (ident.as_str(), value.map(print)))
+        .collect();
+    assert_eq!(
+        enumerators,
+        [
+            ("RED", None),
+            ("GREEN", Some("2".to_string())),
+            ("BLUE", Some("GREEN << 1".to_string()))
+        ]
+    );
+    let (_, blue) = color.enumerators().nth(2).unwrap();
+    assert!(matches!(blue, Some(Expr::Binary(_))));
+    assert_eq!(
+        print(color),
+        "enum color { RED , GREEN = 2 , BLUE = GREEN << 1 , }"
+    );
+
+    let reference = enumeration(&file.declarations[1]);
+    assert!(!reference.is_definition());
+    assert_eq!(reference.enumerators().count(), 0);
+
+    let Item::Fn(is_green) = &file.declarations[2] else {
+        panic!("expected function");
+    };
+    assert!(matches!(
+        &is_green.body.items[0],
+        BlockItem::Stmnt(Stmnt::Return(_))
+    ));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(parse_err("enum { };").0, "expected enumerator");
+    let (msg, pos) = parse_err("enum e { A B };");
+    assert_eq!(msg, "expected `,` or `}` after enumerator");
+    assert_eq!(pos.unwrap().col, 12);
+    let (msg, pos) = parse_err("enum;");
+    assert_eq!(msg, "expected tag or enumerator list");
+    assert_eq!(pos.unwrap().col, 5);
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::declr::Derivation;
+use cyn::specifier::{Specifier, StorageClass};
+use cyn::{to_tokens, Fn, Item};
+
+const INPUT: &str = r#"static inline int (*get_handler(int id))(void) {
+    return 0;
+}
+char *const *f(void) {
+}
+int (*fp)(int);
+int g(int), h;
+int sum(a, b)
+    int a;
+    int b;
+{
+    return a + b;
+}
+"#;
+
+fn function(item: &Item) -> &Fn {
+    match item {
+        Item::Fn(f) => f,
+        _ => panic!("expected function"),
+    }
+}
+
+fn shape(f: &Fn) -> String {
+    f.declarator
+        .derivations()
+        .iter()
+        .map(|derivation| match derivation {
+            Derivation::Pointer(None) => "*",
+            Derivation::Pointer(Some(_)) => "*q",
+            Derivation::Array { .. } => "[]",
+            Derivation::Function(_) => "()",
+            Derivation::IdentList(_) => "(..)",
+        })
+        .collect()
+}
+
+#[test]
+fn definitions() {
+    let file = parse(INPUT);
+
+    let get_handler = function(&file.declarations[0]);
+    assert_eq!(get_handler.ident().as_str(), "get_handler");
+    assert_eq!(shape(get_handler), "()*()");
+    assert_eq!(get_handler.params().unwrap().params.len(), 1);
+    let specifiers = &get_handler.specifiers.as_ref().unwrap().items;
+    assert!(matches!(
+        specifiers[0],
+        Specifier::Storage(StorageClass::Static(_))
+    ));
+
+    let f = function(&file.declarations[1]);
+    assert_eq!(shape(f), "()**q");
+    assert!(f.params().unwrap().is_void());
+
+    assert!(matches!(file.declarations[2], Item::Declr(_)));
+    assert!(matches!(file.declarations[3], Item::Declr(_)));
+
+    let sum = function(&file.declarations[4]);
+    assert_eq!(shape(sum), "(..)");
+    assert!(sum.params().is_none());
+    assert_eq!(sum.ident_list().unwrap().idents.len(), 2);
+    assert_eq!(sum.declr_list.as_ref().unwrap().items.len(), 2);
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let print = |tokens: cyn::TokenStream| tokens.to_string().trim().to_string();
+    let get_handler = function(&file.declarations[0]);
+    assert_eq!(
+        print(to_tokens::to_tokens(&get_handler.declarator)),
+        "( * get_handler ( int id ) ) ( void )"
+    );
+    let sum = function(&file.declarations[4]);
+    assert_eq!(
+        print(to_tokens::to_tokens(sum.declr_list.as_ref().unwrap())),
+        "int a ; int b ;"
+    );
+}
+
+#[test]
+fn errors() {
+    for (input, col) in [("int x {}", 7), ("int (*fp)(int) {}", 16)] {
+        let (msg, pos) = parse_err(input);
+        assert_eq!(msg, "expected token ';', got '{  }'");
+        assert_eq!(pos.unwrap().col, col);
+    }
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::expr::{ExprGeneric, GenericAssociation};
+use cyn::stmnt::StmntExpr;
+use cyn::visit::{self, Visit};
+use cyn::{to_tokens, Expr, Item, Stmnt, TypeName};
+
+const INPUT: &str = r#"typedef long double ld;
+double f(double x) {
+    _Generic(x, float: sqrtf, ld: sqrtl, default: sqrt)(x);
+    return _Generic(x + 1, const char *: 1, int (*)[2]: 2, default: 3) * 2;
+}
+"#;
+
+#[test]
+fn associations() {
+    let file = parse(INPUT);
+    let Item::Fn(f) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    let BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) = &f.body.items[0] else {
+        panic!("expected expression statement");
+    };
+    let Expr::FnCall(call) = &**expr else {
+        panic!("expected call");
+    };
+    let Expr::Generic(generic) = &*call.body else {
+        panic!("expected _Generic");
+    };
+    assert!(matches!(*generic.expr, Expr::Ident(_)));
+    let types: Vec<_> = generic
+        .associations
+        .iter()
+        .map(|association| association.type_name().is_some())
+        .collect();
+    assert_eq!(types, [true, true, false]);
+    assert!(matches!(generic.default_expr(), Some(Expr::Ident(_))));
+}
+
+#[derive(Default)]
+struct Counter {
+    generics: usize,
+    associations: usize,
+    types: usize,
+    idents: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for Counter {
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        if let Expr::Ident(ident) = node {
+            self.idents.push(ident.ident.as_str().to_string());
+        }
+        visit::visit_expr(self, node);
+    }
+
+    fn visit_expr_generic(&mut self, node: &'ast ExprGeneric) {
+        self.generics += 1;
+        visit::visit_expr_generic(self, node);
+    }
+
+    fn visit_generic_association(&mut self, node: &'ast GenericAssociation) {
+        self.associations += 1;
+        visit::visit_generic_association(self, node);
+    }
+
+    fn visit_type_name(&mut self, _node: &'ast TypeName) {
+        self.types += 1;
+    }
+}
+
+#[test]
+fn visitor() {
+    let file = parse(INPUT);
+    let Item::Fn(f) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    let mut counter = Counter::default();
+    for item in &f.body.items {
+        match item {
+            BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => counter.visit_expr(expr),
+            BlockItem::Stmnt(Stmnt::Return(ret)) => counter.visit_expr(ret.expr.as_ref().unwrap()),
+            _ => panic!("expected expression or return"),
+        }
+    }
+    assert_eq!(counter.generics, 2);
+    assert_eq!(counter.associations, 6);
+    assert_eq!(counter.types, 4);
+    assert_eq!(counter.idents, ["x", "sqrtf", "sqrtl", "sqrt", "x", "x"]);
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let Item::Fn(f) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    let BlockItem::Stmnt(Stmnt::Return(ret)) = &f.body.items[1] else {
+        panic!("expected return");
+    };
+    let printed = to_tokens::to_tokens(ret.expr.as_ref().unwrap()).to_string();
+    let printed = printed.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert_eq!(
+        printed,
+        "_Generic ( x + 1 , const char * : 1 , int ( * ) [ 2 ] : 2 , default : 3 ) * 2"
+    );
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        (
+            "_Generic(x);",
+            "expected `,` after controlling expression",
+            10,
+        ),
+        (
+            "_Generic(x, );",
+            "expected generic association after `,`",
+            11,
+        ),
+        (
+            "_Generic(x, int: 1,);",
+            "expected generic association after `,`",
+            19,
+        ),
+        ("_Generic(x, int 1);", "expected token ':', got '1'", 17),
+        (
+            "_Generic(x, default: 1, default: 2);",
+            "duplicate `default` generic association",
+            25,
+        ),
+        (
+            "_Generic(x, int: 1 2);",
+            "expected `,` or `)` after generic association",
+            20,
+        ),
+    ];
+    for (body, msg, col) in cases {
+        let (err, pos) = parse_err(&format!("void f(int x) {{\n{body}\n}}\n"));
+        assert_eq!(err, msg, "{body}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((2, col)), "{body}");
+    }
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{parse, parse_err, parse_with};
+use cyn::initializer::{Designator, Initializer, InitializerList};
+use cyn::{to_tokens, Declr, Dialect, Expr, Item, Lit, ParseConfig};
+
+const INPUT: &str = r#"int a[] = {1, 2, 3,};
+struct point p = { .x = 1, .y = -1 }, q = p;
+int grid[2][2] = { {1, 0}, [1] = {0, 1} };
+struct line l = { .from.x = 1, .to = { 2, 3 }, .pts[2].y = 4 };
+int none[4] = {};
+"#;
+
+fn initializers(item: &Item) -> Vec<&Initializer> {
+    let Item::Declr(Declr { vars, .. }) = item else {
+        panic!("expected declaration");
+    };
+    vars.iter().map(|var| var.initializer().unwrap()).collect()
+}
+
+fn list(initializer: &Initializer) -> &InitializerList {
+    match initializer {
+        Initializer::List(list) => list,
+        Initializer::Expr(_) => panic!("expected initializer list"),
+    }
+}
+
+fn shape(designators: &[Designator]) -> String {
+    designators
+        .iter()
+        .map(|designator| match designator {
+            Designator::Field(field) => format!(".{}", field.ident.as_str()),
+            Designator::Index(_) => "[]".to_string(),
+            Designator::Range(_) => "[...]".to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn lists() {
+    let file = parse(INPUT);
+
+    let a = list(initializers(&file.declarations[0])[0]);
+    assert_eq!(a.items.len(), 3);
+    assert!(a.items.iter().all(|item| item.designation.is_none()));
+
+    let p = initializers(&file.declarations[1]);
+    let shapes: Vec<_> = list(p[0])
+        .items
+        .iter()
+        .map(|item| shape(item.designators()))
+        .collect();
+    assert_eq!(shapes, [".x", ".y"]);
+    assert!(matches!(p[1], Initializer::Expr(expr) if matches!(**expr, Expr::Ident(_))));
+
+    let grid = list(initializers(&file.declarations[2])[0]);
+    let rows: Vec<_> = grid.items.iter().collect();
+    assert!(rows[0].designators().is_empty());
+    assert_eq!(list(&rows[0].initializer).items.len(), 2);
+    assert_eq!(shape(rows[1].designators()), "[]");
+
+    let l = list(initializers(&file.declarations[3])[0]);
+    let shapes: Vec<_> = l
+        .items
+        .iter()
+        .map(|item| shape(item.designators()))
+        .collect();
+    assert_eq!(shapes, [".from.x", ".to", ".pts[].y"]);
+
+    let none = list(initializers(&file.declarations[4])[0]);
+    assert!(none.items.is_empty());
+}
+
+#[test]
+fn gnu_ranges() {
+    let input = "int table[16] = { [0 ... 7] = 1, [8 ... 15] = 2 };\n";
+    let (err, _) = parse_err(input);
+    assert_eq!(err, "range designators are a GNU extension");
+    let config = ParseConfig {
+        dialect: Dialect::Gnu,
+        ..Default::default()
+    };
+    let file = parse_with(input, config);
+    let table = list(initializers(&file.declarations[0])[0]);
+    let shapes: Vec<_> = table
+        .items
+        .iter()
+        .map(|item| shape(item.designators()))
+        .collect();
+    assert_eq!(shapes, ["[...]", "[...]"]);
+}
+
+#[test]
+fn literals() {
+    let file = parse("double d[] = { 1.5f, 1e3, 0x10, 10ULL, 'a', '\\n' };\n");
+    let d = list(initializers(&file.declarations[0])[0]);
+    let lits: Vec<_> = d
+        .items
+        .iter()
+        .map(|item| match &item.initializer {
+            Initializer::Expr(expr) => match &**expr {
+                Expr::Lit(lit) => &lit.lit,
+                _ => panic!("expected literal"),
+            },
+            Initializer::List(_) => panic!("expected expression"),
+        })
+        .collect();
+    assert!(matches!(lits[..2], [Lit::Float(_), Lit::Float(_)]));
+    assert!(matches!(lits[2..4], [Lit::Int(_), Lit::Int(_)]));
+    assert!(matches!(lits[4..], [Lit::Char(_), Lit::Char(_)]));
+    let printed: Vec<_> = lits
+        .iter()
+        .map(|lit| {
+            to_tokens::to_tokens(*lit)
+                .to_string()
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(printed, ["1.5f", "1e3", "0x10", "10ULL", "'a'", "'\\n'"]);
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let l = list(initializers(&file.declarations[3])[0]);
+    let printed = to_tokens::to_tokens(l).to_string();
+    let printed = printed.split_whitespace().collect::<String>();
+    assert_eq!(printed, "{.from.x=1,.to={2,3},.pts[2].y=4}");
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        (
+            "int a[] = { 1 2 };",
+            "expected `,` or `}` after initializer",
+            15,
+        ),
+        ("int a[] = { , };", "expected primary expression.", 13),
+        (
+            "struct s v = { .x 1 };",
+            "expected `=` after designator",
+            16,
+        ),
+        ("struct s v = { .a };", "expected `=` after designator", 16),
+        (
+            "int a[] = { [1 2] = 0 };",
+            "expected `]` after designator",
+            16,
+        ),
+    ];
+    for (input, msg, col) in cases {
+        let (err, pos) = parse_err(input);
+        assert_eq!(err, msg, "{input}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((1, col)), "{input}");
+    }
+}
@@ -0,0 +1,96 @@
+use cyn::emit::{emit, EmitOptions};
+use cyn::{file::File, TokenStream};
+use std::path::PathBuf;
+
+const INPUT: &str = r#"# 0 "main.c"
+# 0 "<built-in>"
+# 0 "<command-line>"
+# 1 "main.c"
+# 1 "/usr/include/foo.h" 1 3
+int foo;
+int bar(int x) { return x @ 1; }
+# 2 "main.c" 2
+
+int baz(int x) { return x y; }
+"#;
+
+#[test]
+fn errors_point_into_original_files() {
+    let input = INPUT.replace(" @ 1", "");
+    let ts = TokenStream::from_str(&input).unwrap();
+    let err = match ts.parse::<File>() {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    let pos = err.pos().unwrap();
+    assert_eq!(err.file(), &Some(PathBuf::from("main.c")));
+    assert_eq!(pos.row, 3);
+    assert!(!pos.in_system_header());
+}
+
+#[test]
+fn system_header_flag() {
+    let err = match TokenStream::from_str(INPUT) {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    let pos = err.pos().unwrap();
+    assert_eq!(err.file(), &Some(PathBuf::from("/usr/include/foo.h")));
+    assert_eq!((pos.row, pos.col), (2, 27));
+    assert!(pos.in_system_header());
+}
+
+#[test]
+fn line_directive() {
+    let input = "int a;\n#line 100 \"gen.c\"\nint b @;\n";
+    let err = match TokenStream::from_str(input) {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    assert_eq!(err.file(), &Some(PathBuf::from("gen.c")));
+    assert_eq!(err.pos().unwrap().row, 100);
+
+    let input = "#line 7\nint b @;\n";
+    let err = match TokenStream::from_str(input) {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    assert_eq!(err.file(), &None);
+    assert_eq!(err.pos().unwrap().row, 7);
+}
+
+#[test]
+fn malformed_marker() {
+    for input in ["# 1 \"foo", "# 1 \"foo\nint x;\n"] {
+        let err = match TokenStream::from_str(input) {
+            Ok(_) => panic!("expected error"),
+            Err(err) => err,
+        };
+        assert_eq!(err.file(), &None);
+        assert_eq!(err.pos().unwrap().row, 1);
+    }
+
+    let input = "# 5 \"a.c\"\n# 1 \"b.c\" 1 \"\nint x;\n";
+    let err = match TokenStream::from_str(input) {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    assert_eq!(err.file(), &Some(PathBuf::from("a.c")));
+    assert_eq!(err.pos().unwrap().row, 5);
+}
+
+#[test]
+fn emit_round_trip() {
+    let input = INPUT.replace(" @ 1", "");
+    let ts = TokenStream::from_str(&input).unwrap();
+    let out = emit(&ts, &EmitOptions::default());
+    assert_eq!(
+        out,
+        r#"# 1 "/usr/include/foo.h" 1 3
+int foo;
+int bar(int x) { return x; }
+# 3 "main.c" 2
+int baz(int x) { return x y; }
+"#
+    );
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::declr::Derivation;
+use cyn::func::FnParams;
+use cyn::{to_tokens, FnParam, Item};
+
+const INPUT: &str = r#"typedef unsigned long size_t;
+void qsort(void *base, size_t n, size_t sz, int (*cmp)(const void *, const void *));
+int main(int argc, char *argv[]) {
+    return 0;
+}
+void fill(int a[static 10], int b[const], char [static restrict 4]);
+int none(void);
+"#;
+
+fn params(item: &Item) -> &FnParams {
+    match item {
+        Item::Declr(declr) => {
+            let declarator = declr.vars.iter().next().unwrap().declarator();
+            match declarator.derivations()[0] {
+                Derivation::Function(params) => params,
+                _ => panic!("expected function"),
+            }
+        }
+        Item::Fn(f) => f.params().expect("expected parameters"),
+        _ => panic!("expected function"),
+    }
+}
+
+fn names(params: &FnParams) -> Vec<Option<&str>> {
+    params
+        .params
+        .iter()
+        .map(|param| match param {
+            FnParam::Named(named) => Some(named.declarator.ident().as_str()),
+            FnParam::Unnamed(_) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn prototypes() {
+    let file = parse(INPUT);
+    let qsort = params(&file.declarations[1]);
+    assert_eq!(
+        names(qsort),
+        [Some("base"), Some("n"), Some("sz"), Some("cmp")]
+    );
+    let FnParam::Named(cmp) = qsort.params.iter().last().unwrap() else {
+        panic!("expected named parameter");
+    };
+    let derivations = cmp.declarator.derivations();
+    assert!(matches!(
+        derivations[..],
+        [Derivation::Pointer(_), Derivation::Function(_)]
+    ));
+
+    let main = params(&file.declarations[2]);
+    assert_eq!(names(main), [Some("argc"), Some("argv")]);
+
+    let fill = params(&file.declarations[3]);
+    assert_eq!(names(fill), [Some("a"), Some("b"), None]);
+    let statics: Vec<_> = fill
+        .params
+        .iter()
+        .map(|param| match param {
+            FnParam::Named(named) => named.declarator.derivations()[0],
+            FnParam::Unnamed(ty) => ty.derivations()[0],
+        })
+        .map(|derivation| match derivation {
+            Derivation::Array {
+                is_static,
+                qualifier_list,
+                is_star: false,
+                len,
+            } => (is_static, qualifier_list.is_some(), len.is_some()),
+            _ => panic!("expected array"),
+        })
+        .collect();
+    assert_eq!(
+        statics,
+        [
+            (true, false, true),
+            (false, true, false),
+            (true, true, true)
+        ]
+    );
+
+    assert!(params(&file.declarations[4]).is_void());
+    assert!(!main.is_void());
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed = to_tokens::to_tokens(params(&file.declarations[3])).to_string();
+    assert_eq!(
+        printed.trim(),
+        "( int a [ static 10 ] , int b [ const ] , char [ static restrict 4 ] )"
+    );
+}
+
+#[test]
+fn unspecified_vla_size() {
+    let file = parse(
+        "int f(int n, int a[*]);
+int g(int n, int [const *][*], int b[*n]);
+",
+    );
+    let stars = |params: &FnParams| -> Vec<Vec<bool>> {
+        params
+            .params
+            .iter()
+            .map(|param| match param {
+                FnParam::Named(named) => named.declarator.derivations(),
+                FnParam::Unnamed(ty) => ty.derivations(),
+            })
+            .map(|derivations| {
+                derivations
+                    .iter()
+                    .filter_map(|derivation| match derivation {
+                        Derivation::Array { is_star, .. } => Some(*is_star),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    assert_eq!(stars(params(&file.declarations[0])), [vec![], vec![true]]);
+    // `*n` is the length, not the unspecified size
+    assert_eq!(
+        stars(params(&file.declarations[1])),
+        [vec![], vec![true, true], vec![false]]
+    );
+    let printed = to_tokens::to_tokens(params(&file.declarations[1])).to_string();
+    assert_eq!(
+        printed.trim(),
+        "( int n , int [ const * ] [ * ] , int b [ * n ] )"
+    );
+}
+
+#[test]
+fn errors() {
+    let (msg, pos) = parse_err("int f(int a[static]);");
+    assert_eq!(msg, "expected array size after `static`");
+    assert_eq!(pos.unwrap().col, 13);
+    assert_eq!(
+        parse_err("int f(int a[static *]);").0,
+        "expected array size after `static`"
+    );
+    let (msg, pos) = parse_err("int f(int a[3 4]);");
+    assert_eq!(msg, "expected `]`");
+    assert_eq!(pos.unwrap().col, 15);
+    assert_eq!(
+        parse_err("int f(void, int);").0,
+        "`void` must be the only parameter"
+    );
+}
+
+#[test]
+fn variadic() {
+    let file = parse(
+        "int printf(const char *fmt, ...);\nint any(...);\nint sum(int n, ...) {\n    return n;\n}\n",
+    );
+    let printf = params(&file.declarations[0]);
+    assert!(printf.is_variadic());
+    assert_eq!(names(printf), [Some("fmt")]);
+    assert!(params(&file.declarations[1]).is_variadic());
+    let Item::Fn(sum) = &file.declarations[2] else {
+        panic!("expected function");
+    };
+    assert!(sum.is_variadic());
+    assert!(!params(&parse(INPUT).declarations[2]).is_variadic());
+
+    let printed = to_tokens::to_tokens(printf).to_string();
+    assert_eq!(printed.trim(), "( const char * fmt , ... )");
+
+    for (input, col) in [("int f(..., int);", 10), ("int f(int, ...,);", 15)] {
+        let (msg, pos) = parse_err(input);
+        assert_eq!(msg, "expected `)` after `...`");
+        assert_eq!(pos.unwrap().col, col);
+    }
+    for (input, col) in [("int f(void, ...);", 7), ("int f(int, void);", 12)] {
+        let (msg, pos) = parse_err(input);
+        assert_eq!(msg, "`void` must be the only parameter");
+        assert_eq!(pos.unwrap().col, col);
+    }
+}
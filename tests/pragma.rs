@@ -0,0 +1,105 @@
+use cyn::block::BlockItem;
+use cyn::file::File;
+use cyn::pp::Preprocessor;
+use cyn::pragma::Pragma;
+use cyn::{Ident, Item, Parse, ParseConfig, ParseStream, Result, TokenStream};
+
+const INPUT: &str = r#"#pragma once
+#define DIAG(x) _Pragma(#x)
+DIAG(GCC diagnostic ignored "-Wformat") int x;
+int f(int y) {
+#pragma pack(push, 1)
+    return y;
+}
+#pragma redefine_extname old new
+"#;
+
+fn pragma(item: &Item) -> &Pragma {
+    match item {
+        Item::Pragma(pragma) => pragma,
+        _ => panic!("expected pragma"),
+    }
+}
+
+fn names(pragma: &Pragma) -> (Option<&str>, &str, String) {
+    (
+        pragma.namespace.as_ref().map(Ident::as_str),
+        pragma.name.as_str(),
+        pragma.args.to_string().trim().to_string(),
+    )
+}
+
+#[test]
+fn pragmas() {
+    let ts = Preprocessor::default().preprocess(INPUT).unwrap();
+    let file: File = ts.parse().unwrap();
+    let items = &file.declarations;
+    assert_eq!(names(pragma(&items[0])), (None, "once", String::new()));
+    assert_eq!(
+        names(pragma(&items[1])),
+        (
+            Some("GCC"),
+            "diagnostic",
+            r#"ignored "-Wformat""#.to_string()
+        )
+    );
+    assert!(matches!(items[2], Item::Declr(_)));
+    match &items[3] {
+        Item::Fn(f) => match &f.body.items[0] {
+            BlockItem::Pragma(pragma) => {
+                assert_eq!(names(pragma), (None, "pack", "( push , 1 )".to_string()))
+            }
+            _ => panic!("expected pragma"),
+        },
+        _ => panic!("expected function"),
+    }
+    assert_eq!(
+        names(pragma(&items[4])),
+        (None, "redefine_extname", "old new".to_string())
+    );
+    let printed = cyn::to_tokens::to_tokens(&file).to_string();
+    assert!(printed.contains("#pragma GCC diagnostic ignored \"-Wformat\"\n"));
+}
+
+struct Diagnostic {
+    action: Vec<Ident>,
+}
+
+impl Parse for Diagnostic {
+    fn parse(parse: ParseStream) -> Result<Self> {
+        let mut action = vec![];
+        while parse.peek::<Ident>() {
+            action.push(parse.parse()?);
+        }
+        Ok(Self { action })
+    }
+}
+
+#[test]
+fn registry() {
+    let mut config = ParseConfig::default();
+    config.pragmas.register::<Diagnostic>("GCC");
+    let ts = TokenStream::from_str("#pragma GCC diagnostic push\n").unwrap();
+    let file: File = ts.parse_with_config(config.clone()).unwrap();
+    let diagnostic = pragma(&file.declarations[0]).get::<Diagnostic>().unwrap();
+    let action: Vec<_> = diagnostic.action.iter().map(Ident::as_str).collect();
+    assert_eq!(action, ["diagnostic", "push"]);
+
+    let ts = TokenStream::from_str("#pragma GCC diagnostic ignored \"-Wall\"\n").unwrap();
+    let err = match ts.parse_with_config::<File>(config) {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    assert_eq!(err.msg(), "unexpected token in #pragma GCC");
+    assert_eq!(err.pos().unwrap().col, 32);
+}
+
+#[test]
+fn invalid_pragma_operator() {
+    let err = match Preprocessor::default().preprocess("int x;\n_Pragma(once)\n") {
+        Ok(_) => panic!("expected error"),
+        Err(err) => err,
+    };
+    assert_eq!(err.msg(), "_Pragma takes a parenthesized string literal");
+    assert_eq!(err.pos().unwrap().row, 2);
+}
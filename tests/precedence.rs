@@ -0,0 +1,111 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::stmnt::StmntExpr;
+use cyn::{to_tokens, Expr, Item, Stmnt, ToTokens};
+
+/// Prints the expression of each statement of `f` with every operation
+/// parenthesized.
+fn grouped(body: &str) -> Vec<String> {
+    let input = format!("void f(void) {{\n{body}\n}}\n");
+    let file = parse(&input);
+    let Item::Fn(f) = &file.declarations[0] else {
+        panic!("expected function");
+    };
+    f.body
+        .items
+        .iter()
+        .map(|item| match item {
+            BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => group(expr),
+            _ => panic!("expected expression statement"),
+        })
+        .collect()
+}
+
+fn op<T: ToTokens>(op: &T) -> String {
+    to_tokens::to_tokens(op).to_string()
+}
+
+fn group(expr: &Expr) -> String {
+    match expr {
+        Expr::Binary(e) => format!("({} {} {})", group(&e.lhs), op(&e.op).trim(), group(&e.rhs)),
+        Expr::Assing(e) => format!("({} {} {})", group(&e.lhs), op(&e.op).trim(), group(&e.rhs)),
+        Expr::Ternary(e) => format!(
+            "({} ? {} : {})",
+            group(&e.cond),
+            group(&e.true_expr),
+            group(&e.false_expr)
+        ),
+        Expr::Comma(e) => format!("({}, {})", group(&e.left), group(&e.right)),
+        Expr::Unary(e) => format!("{}{}", op(&e.op).trim(), group(&e.expr)),
+        _ => op(expr).trim().to_string(),
+    }
+}
+
+#[test]
+fn left_associative() {
+    assert_eq!(
+        grouped("a - b - c;\na / b * c;\na << b >> c;\na < b == c > d;\na && b && c || d;"),
+        [
+            "((a - b) - c)",
+            "((a / b) * c)",
+            "((a << b) >> c)",
+            "((a < b) == (c > d))",
+            "(((a && b) && c) || d)",
+        ]
+    );
+}
+
+#[test]
+fn precedence_levels() {
+    assert_eq!(
+        grouped("a + b * c - d;\na | b ^ c & d;\na & b == c;\n-a * b + c;\na || b && c | d;"),
+        [
+            "((a + (b * c)) - d)",
+            "(a | (b ^ (c & d)))",
+            "(a & (b == c))",
+            "((-a * b) + c)",
+            "(a || (b && (c | d)))",
+        ]
+    );
+}
+
+#[test]
+fn right_associative() {
+    assert_eq!(
+        grouped("a = b = c + 1;\na += b ? c : d ? e : f;\na ? b, c : d;\na, b = c, d;"),
+        [
+            "(a = (b = (c + 1)))",
+            "(a += (b ? c : (d ? e : f)))",
+            "(a ? (b, c) : d)",
+            "((a, (b = c)), d)",
+        ]
+    );
+}
+
+#[test]
+fn deep_nesting() {
+    let depth = 32;
+    let body = format!("x = {}a = b{};", "(".repeat(depth), ")".repeat(depth));
+    let start = std::time::Instant::now();
+    let grouped = grouped(&body);
+    assert!(start.elapsed().as_secs() < 2, "took {:?}", start.elapsed());
+    assert!(grouped[0].starts_with("(x = ("), "{}", grouped[0]);
+}
+
+#[test]
+fn assignment_errors() {
+    let lhs = "expected unary expression before assignment operator";
+    let cases = [
+        ("a + b = c;", lhs, 7),
+        ("a ? b : c = d;", lhs, 11),
+        ("(int)a = b;", lhs, 8),
+        ("a, b = c = ;", "expected primary expression.", 12),
+    ];
+    for (body, msg, col) in cases {
+        let (err, pos) = parse_err(&format!("void f(void) {{\n{body}\n}}\n"));
+        assert_eq!(err, msg, "{body}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((2, col)), "{body}");
+    }
+}
@@ -0,0 +1,223 @@
+use cyn::pp::Preprocessor;
+use cyn::Dialect;
+
+fn pp(dialect: Dialect, input: &str) -> String {
+    let ts = Preprocessor::new(dialect).preprocess(input).unwrap();
+    ts.to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn pp_err(input: &str) -> (String, usize, usize) {
+    let err = match Preprocessor::default().preprocess(input) {
+        Ok(ts) => panic!("expected error, got {ts}"),
+        Err(err) => err,
+    };
+    let pos = err.pos().unwrap();
+    (err.msg().clone(), pos.row, pos.col)
+}
+
+#[test]
+fn variadic() {
+    let input = r#"
+#define LOG(fmt, ...) printf(fmt, __VA_ARGS__)
+#define ALL(...) f(__VA_ARGS__)
+LOG("%d %d", 1, (2, 3));
+ALL();
+ALL(a, b);
+"#;
+    assert_eq!(
+        pp(Dialect::Iso, input),
+        r#"printf ( "%d %d" , 1 , ( 2 , 3 ) ) ; f ( ) ; f ( a , b ) ;"#
+    );
+}
+
+#[test]
+fn va_opt() {
+    let input = r#"
+#define LOG(fmt, ...) printf(fmt __VA_OPT__(,) __VA_ARGS__)
+#define EMPTY
+LOG("a");
+LOG("a", EMPTY);
+LOG("a", 1, 2);
+"#;
+    assert_eq!(
+        pp(Dialect::Iso, input),
+        r#"printf ( "a" ) ; printf ( "a" ) ; printf ( "a" , 1 , 2 ) ;"#
+    );
+}
+
+#[test]
+fn gnu_comma_swallowing() {
+    let input = r#"
+#define LOG(fmt, ...) printf(fmt, ## __VA_ARGS__)
+LOG("a");
+LOG("a", 1, 2);
+"#;
+    assert_eq!(
+        pp(Dialect::Gnu, input),
+        r#"printf ( "a" ) ; printf ( "a" , 1 , 2 ) ;"#
+    );
+    let (msg, ..) = pp_err(input);
+    assert_eq!(
+        msg,
+        r#"pasting "," and "1" does not give a valid preprocessing token"#
+    );
+}
+
+#[test]
+fn gnu_named_variadic() {
+    let input = r#"
+#define LOG(fmt, args...) printf(fmt, args)
+#define ALL(args...) f(args)
+#define OPT(fmt, rest...) printf(fmt, ## rest)
+LOG("%d %d", 1, 2);
+ALL();
+OPT("a");
+OPT("a", 1);
+"#;
+    assert_eq!(
+        pp(Dialect::Gnu, input),
+        r#"printf ( "%d %d" , 1 , 2 ) ; f ( ) ; printf ( "a" ) ; printf ( "a" , 1 ) ;"#
+    );
+    assert_eq!(
+        pp_err("#define F(args...) __VA_ARGS__\n").0,
+        "__VA_ARGS__ can only appear in the expansion of a C99 variadic macro"
+    );
+    assert_eq!(
+        pp_err("#define F(args..., b) b\n"),
+        (r#"expected ')' after "...""#.to_string(), 1, 18)
+    );
+}
+
+#[test]
+fn stringify_and_paste() {
+    let input = r#"
+#define STR(x) #x
+#define XSTR(x) STR(x)
+#define CAT(a, b) a ## b
+#define TWO 2
+STR(a  +  "b\n") XSTR(TWO) CAT(x, TWO) CAT(, y) CAT(-, >)
+"#;
+    assert_eq!(pp(Dialect::Iso, input), r#""a + \"b\\n\"" "2" xTWO y ->"#);
+
+    let input = "#define STR(x) #x\nSTR(0x10) STR(1.5f) STR(10ULL) STR('\\n') STR('\"')";
+    assert_eq!(
+        pp(Dialect::Iso, input),
+        r#""0x10" "1.5f" "10ULL" "'\\n'" "'\"'""#
+    );
+}
+
+#[test]
+fn no_recursion() {
+    let input = "#define foo foo bar\n#define f(x) x f\nfoo f(f)(1)";
+    assert_eq!(pp(Dialect::Iso, input), "foo bar f f ( 1 )");
+}
+
+#[test]
+fn conditionals() {
+    let input = r#"
+#define X 3
+#if defined(X) && X > 2
+a
+#elif 1 / 0
+b
+#else
+c
+#endif
+#ifndef X
+d
+#elifdef X
+e
+#endif
+"#;
+    assert_eq!(pp(Dialect::Iso, input), "a e");
+}
+
+#[test]
+fn unsigned_arithmetic() {
+    let checks = [
+        ("-1 > 0u", true),
+        ("-1 > 0", false),
+        ("~0u == 0xFFFFFFFFFFFFFFFF", true),
+        ("0u - 1 == 18446744073709551615u", true),
+        ("0xFFFFFFFFFFFFFFFF > 0", true),
+        ("-1 / 2u > 0", true),
+        ("-1 >> 1 < 0", true),
+        ("-1u >> 63 == 1", true),
+        ("(1 ? -1 : 0u) > 0", true),
+        ("-9223372036854775807 - 1 < 0", true),
+        ("9223372036854775807 + 1 < 0", true),
+    ];
+    for (expr, expected) in checks {
+        let input = format!(
+            "#if {expr}
+yes
+#else
+no
+#endif
+"
+        );
+        let expected = if expected { "yes" } else { "no" };
+        assert_eq!(pp(Dialect::Iso, &input), expected, "{expr}");
+    }
+}
+
+#[test]
+fn argument_count_errors() {
+    let input = "#define F(a, b) a + b\nint x = F(1,\n          2,\n          3);\n";
+    assert_eq!(
+        pp_err(input),
+        (
+            r#"macro "F" passed 3 arguments, but takes just 2"#.to_string(),
+            3,
+            12
+        )
+    );
+    let input = "#define F(a, b) a + b\nint x = F(1\n);\n";
+    assert_eq!(
+        pp_err(input),
+        (
+            r#"macro "F" requires 2 arguments, but only 1 given"#.to_string(),
+            3,
+            1
+        )
+    );
+    let input = "#define F(a, b, ...) a\nint x = F(1);\n";
+    assert_eq!(pp_err(input).1, 2);
+    assert_eq!(pp(Dialect::Iso, "#define F(a, ...) a\nF(1)"), "1");
+}
+
+#[test]
+fn unbalanced_invocation() {
+    let input = "#define F(a) a\nint x = F((1,\n  2);\nint y;\n";
+    assert_eq!(
+        pp_err(input),
+        (
+            r#"unterminated argument list invoking macro "F""#.to_string(),
+            2,
+            9
+        )
+    );
+}
+
+#[test]
+fn definition_errors() {
+    assert_eq!(
+        pp_err("#define F(x) #y\n").0,
+        "'#' is not followed by a macro parameter"
+    );
+    assert_eq!(
+        pp_err("#define F(x) x __VA_ARGS__\n").0,
+        "__VA_ARGS__ can only appear in the expansion of a variadic macro"
+    );
+    assert_eq!(
+        pp_err("#define F(...) __VA_OPT__(a\n").0,
+        "unterminated __VA_OPT__"
+    );
+    assert_eq!(
+        pp_err("#define F(x) ## x\n").0,
+        "'##' cannot appear at either end of a macro expansion"
+    );
+}
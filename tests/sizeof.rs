@@ -0,0 +1,121 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::expr::AlignofKeyword;
+use cyn::file::File;
+use cyn::stmnt::StmntExpr;
+use cyn::{to_tokens, Expr, Item, Stmnt};
+
+const INPUT: &str = r#"typedef struct point { int x, y; } point;
+void f(int *p, int n) {
+    sizeof(int);
+    sizeof(unsigned long [4]);
+    sizeof(point *);
+    sizeof *p;
+    sizeof (n);
+    sizeof n + 1;
+    sizeof (point){ 1, 2 };
+    sizeof sizeof(char);
+    _Alignof(unsigned long);
+    alignof(struct point);
+}
+"#;
+
+fn exprs(file: &File) -> Vec<&Expr> {
+    let Item::Fn(f) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    f.body
+        .items
+        .iter()
+        .map(|item| match item {
+            BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => &**expr,
+            _ => panic!("expected expression statement"),
+        })
+        .collect()
+}
+
+fn operand(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Sizeof(sizeof) => &sizeof.expr,
+        _ => panic!("expected sizeof expression"),
+    }
+}
+
+#[test]
+fn operands() {
+    let file = parse(INPUT);
+    let exprs = exprs(&file);
+
+    for expr in &exprs[..3] {
+        assert!(matches!(expr, Expr::SizeofType(_)));
+    }
+    let Expr::SizeofType(array) = exprs[1] else {
+        unreachable!();
+    };
+    assert_eq!(array.ty.derivations().len(), 1);
+
+    assert!(matches!(operand(exprs[3]), Expr::Unary(_)));
+    assert!(matches!(operand(exprs[4]), Expr::Paren(_)));
+    let Expr::Binary(sum) = exprs[5] else {
+        panic!("expected `(sizeof n) + 1`");
+    };
+    assert!(matches!(*sum.lhs, Expr::Sizeof(_)));
+    assert!(matches!(operand(exprs[6]), Expr::Initialize(_)));
+    assert!(matches!(operand(exprs[7]), Expr::SizeofType(_)));
+
+    let Expr::Alignof(alignof) = exprs[8] else {
+        panic!("expected _Alignof");
+    };
+    assert!(matches!(alignof.alignof, AlignofKeyword::Alignof(_)));
+    assert_eq!(alignof.ty.specifiers.items.len(), 2);
+    let Expr::Alignof(alignof) = exprs[9] else {
+        panic!("expected alignof");
+    };
+    assert!(matches!(alignof.alignof, AlignofKeyword::C23(_)));
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = exprs(&file)
+        .into_iter()
+        .map(|expr| {
+            let printed = to_tokens::to_tokens(expr).to_string();
+            printed.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "sizeof ( int )",
+            "sizeof ( unsigned long [ 4 ] )",
+            "sizeof ( point * )",
+            "sizeof * p",
+            "sizeof ( n )",
+            "sizeof n + 1",
+            "sizeof ( point ) { 1 , 2 }",
+            "sizeof sizeof ( char )",
+            "_Alignof ( unsigned long )",
+            "alignof ( struct point )",
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    let type_name = "expected `)` after type name";
+    let cases = [
+        ("sizeof;", "expected primary expression.", 7),
+        ("_Alignof(x);", "expected at least one specifier", 10),
+        ("alignof int;", "expected (", 9),
+        ("sizeof(int x);", type_name, 12),
+        ("_Alignof(int x);", type_name, 14),
+    ];
+    for (body, msg, col) in cases {
+        let (err, pos) = parse_err(&format!("void f(void) {{\n{body}\n}}\n"));
+        assert_eq!(err, msg, "{body}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((2, col)), "{body}");
+    }
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::file::File;
+use cyn::loops::ForInit;
+use cyn::{to_tokens, Item, Stmnt};
+
+const INPUT: &str = r#"typedef int T;
+int f(int n) {
+    if (n) n--; else n++;
+    switch (n) case 1: n = 2;
+    while (n) n--;
+    do n++; while (n < 3);
+    for (n = 0; n < 3; n++) ;
+    for (int T = 0; T < 3; T++) T * 2;
+    for (;;) break;
+    T * p;
+    ;
+    again: if (n) goto again;
+    while (n) { continue; }
+    return;
+    return n;
+}
+"#;
+
+fn stmnts(file: &File) -> Vec<&BlockItem> {
+    let Item::Fn(f) = &file.declarations[1] else {
+        panic!("expected function");
+    };
+    f.body.items.iter().collect()
+}
+
+fn stmnt(item: &BlockItem) -> &Stmnt {
+    match item {
+        BlockItem::Stmnt(stmnt) => stmnt,
+        _ => panic!("expected statement"),
+    }
+}
+
+#[test]
+fn kinds() {
+    let file = parse(INPUT);
+    let items = stmnts(&file);
+    let kinds: Vec<_> = items
+        .iter()
+        .map(|item| match item {
+            BlockItem::Declr(_) => "declr",
+            BlockItem::Stmnt(stmnt) => match stmnt {
+                Stmnt::If(_) => "if",
+                Stmnt::Switch(_) => "switch",
+                Stmnt::While(_) => "while",
+                Stmnt::DoWhile(_) => "do",
+                Stmnt::For(_) => "for",
+                Stmnt::Null(_) => ";",
+                Stmnt::Label(_) => "label",
+                Stmnt::Return(_) => "return",
+                _ => "other",
+            },
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "if", "switch", "while", "do", "for", "for", "for", "declr", ";", "label", "while",
+            "return", "return"
+        ]
+    );
+
+    let Stmnt::If(if_stmnt) = stmnt(items[0]) else {
+        unreachable!();
+    };
+    assert!(if_stmnt.else_stmnt.is_some());
+
+    let Stmnt::For(counting) = stmnt(items[4]) else {
+        unreachable!();
+    };
+    assert!(matches!(counting.initial, ForInit::Expr(_)));
+    assert!(matches!(*counting.stmnt, Stmnt::Null(_)));
+
+    let Stmnt::For(declaring) = stmnt(items[5]) else {
+        unreachable!();
+    };
+    assert!(matches!(declaring.initial, ForInit::Declr(_)));
+    // the loop variable hides the typedef only inside the loop
+    assert!(matches!(*declaring.stmnt, Stmnt::Expr(_)));
+
+    let Stmnt::For(forever) = stmnt(items[6]) else {
+        unreachable!();
+    };
+    assert!(forever.condition.is_none() && forever.post.is_none());
+    assert!(matches!(*forever.stmnt, Stmnt::Break(_)));
+
+    let Stmnt::Return(bare) = stmnt(items[11]) else {
+        unreachable!();
+    };
+    assert!(bare.expr.is_none());
+    let Stmnt::Return(value) = stmnt(items[12]) else {
+        unreachable!();
+    };
+    assert!(value.expr.is_some());
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = stmnts(&file)
+        .into_iter()
+        .map(|item| {
+            let printed = to_tokens::to_tokens(item).to_string();
+            printed.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "if ( n ) n -- ; else n ++ ;",
+            "switch ( n ) case 1 : n = 2 ;",
+            "while ( n ) n -- ;",
+            "do n ++ ; while ( n < 3 ) ;",
+            "for ( n = 0 ; n < 3 ; n ++ ) ;",
+            "for ( int T = 0 ; T < 3 ; T ++ ) T * 2 ;",
+            "for ( ; ; ) break ;",
+            "T * p ;",
+            ";",
+            "again : if ( n ) goto again ;",
+            "while ( n ) { continue ; }",
+            "return ;",
+            "return n ;",
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    let for_condition = "expected `;` after for condition";
+    let while_condition = "expected `)` after while condition";
+    let cases = [
+        ("for (n = 0; n < 3) ;", for_condition, (2, 13)),
+        ("for (n = 0;) ;", for_condition, (2, 6)),
+        (
+            "for (n; n; n; n) ;",
+            "expected `)` after for clauses",
+            (2, 13),
+        ),
+        (
+            "do n++ while (n);",
+            "expected token ';', got 'while'",
+            (2, 8),
+        ),
+        ("return n\nn++;", "expected token ';', got 'n'", (3, 1)),
+        ("if n;", "expected (", (2, 4)),
+        ("if (n n) ;", "expected `)` after if condition", (2, 7)),
+        ("while (n n) ;", while_condition, (2, 10)),
+        ("do ; while (n n);", while_condition, (2, 15)),
+    ];
+    for (body, msg, at) in cases {
+        let (err, pos) = parse_err(&format!("void f(int n) {{\n{body}\n}}\n"));
+        assert_eq!(err, msg, "{body}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some(at), "{body}");
+    }
+}
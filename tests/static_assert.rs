@@ -0,0 +1,80 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::specifier::{Specifier, TypeSpecifier};
+use cyn::struct_union::Member;
+use cyn::{to_tokens, Declr, Item};
+
+const INPUT: &str = r#"_Static_assert(VERSION >= 2, "version too old");
+_Static_assert(FLAGS & 1);
+struct header {
+    int kind;
+    _Static_assert(KIND_MAX < 256, "kind fits in a byte");
+};
+int f(void) {
+    _Static_assert(1);
+    return 0;
+}
+"#;
+
+#[test]
+fn scopes() {
+    let file = parse(INPUT);
+    let Item::StaticAssert(version) = &file.declarations[0] else {
+        panic!("expected static assertion");
+    };
+    assert!(version.message().is_some());
+    let Item::StaticAssert(flags) = &file.declarations[1] else {
+        panic!("expected static assertion");
+    };
+    assert!(flags.message().is_none());
+
+    let Item::Declr(Declr { specifiers, .. }) = &file.declarations[2] else {
+        panic!("expected declaration");
+    };
+    let Specifier::Type(TypeSpecifier::Struct(header)) = &specifiers.items[0] else {
+        panic!("expected struct");
+    };
+    let members = &header.members.as_ref().unwrap().items;
+    assert!(matches!(&members[1], Member::StaticAssert(assert) if assert.message().is_some()));
+
+    let Item::Fn(f) = &file.declarations[3] else {
+        panic!("expected function");
+    };
+    assert!(matches!(f.body.items[0], BlockItem::StaticAssert(_)));
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = file.declarations[..2]
+        .iter()
+        .map(|item| to_tokens::to_tokens(item).to_string().trim().to_string())
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "_Static_assert ( VERSION >= 2 , \"version too old\" ) ;",
+            "_Static_assert ( FLAGS & 1 ) ;"
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        parse_err("_Static_assert(1, \"a\")").0,
+        "expected token ';', got 'end of buffer'"
+    );
+    assert_eq!(
+        parse_err("_Static_assert();").0,
+        "expected primary expression."
+    );
+    let (msg, pos) = parse_err("_Static_assert(1, 2);");
+    assert_eq!(msg, "expected string literal");
+    assert_eq!(pos.unwrap().col, 19);
+    let (msg, pos) = parse_err("void f(void) { _Static_assert(1 \"a\"); }");
+    assert_eq!(msg, "expected `,` or `)` in _Static_assert");
+    assert_eq!(pos.unwrap().col, 33);
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::parse;
+use cyn::specifier::{Specifier, TypeSpecifier};
+use cyn::struct_union::{Member, MemberDeclr, MemberList, StructSpecifier};
+use cyn::{to_tokens, Declr, Item};
+
+const INPUT: &str = r#"struct point { int x, y; };
+struct node *head;
+struct packet {
+    unsigned kind : 3, : 0;
+    union { int i; float f; };
+    struct { int a; } named;
+    _Static_assert(KIND_BITS == 3, "three kind bits");
+    char data[];
+} p;
+"#;
+
+fn record(item: &Item) -> &StructSpecifier {
+    let Item::Declr(Declr { specifiers, .. }) = item else {
+        panic!("expected declaration");
+    };
+    match &specifiers.items[0] {
+        Specifier::Type(TypeSpecifier::Struct(record)) => record,
+        _ => panic!("expected struct"),
+    }
+}
+
+fn fields(members: &MemberList) -> Vec<&MemberDeclr> {
+    members
+        .items
+        .iter()
+        .filter_map(|member| match member {
+            Member::Field(field) => Some(field),
+            Member::StaticAssert(_) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn members() {
+    let file = parse(INPUT);
+    let point = record(&file.declarations[0]);
+    assert_eq!(point.tag.as_ref().unwrap().as_str(), "point");
+    let names: Vec<_> = fields(point.members.as_ref().unwrap())[0]
+        .declarators
+        .iter()
+        .map(|declarator| declarator.ident().unwrap().as_str())
+        .collect();
+    assert_eq!(names, ["x", "y"]);
+
+    assert!(!record(&file.declarations[1]).is_definition());
+
+    let packet = record(&file.declarations[2]).members.as_ref().unwrap();
+    assert!(matches!(packet.items[3], Member::StaticAssert(_)));
+    let fields = fields(packet);
+    let bit_fields: Vec<_> = fields[0].declarators.iter().collect();
+    assert_eq!(bit_fields[0].ident().unwrap().as_str(), "kind");
+    assert!(bit_fields[0].bit_field.is_some());
+    assert!(bit_fields[1].declarator.is_none());
+    assert_eq!(fields[1].anonymous().unwrap().items.len(), 2);
+    assert!(fields[2].anonymous().is_none());
+    let data = packet.flexible_array().unwrap();
+    assert_eq!(data.ident().unwrap().as_str(), "data");
+    assert!(point.members.as_ref().unwrap().flexible_array().is_none());
+}
+
+#[test]
+fn flexible_array_members() {
+    let flexible = |input: &str| {
+        let file = parse(input);
+        let members = record(&file.declarations[0]).members.as_ref().unwrap();
+        members
+            .flexible_array()
+            .map(|member| member.ident().unwrap().as_str().to_string())
+    };
+    assert_eq!(
+        flexible("struct s { int n; char *d[]; };").as_deref(),
+        Some("d")
+    );
+    assert_eq!(flexible("struct s { int n, d[]; };").as_deref(), Some("d"));
+    assert_eq!(
+        flexible("struct s { int n; int (d)[]; };").as_deref(),
+        Some("d")
+    );
+    assert_eq!(flexible("struct s { int n; int (*p)[]; };"), None);
+    assert_eq!(flexible("struct s { int d[], n; };"), None);
+    assert_eq!(flexible("struct s { char d[]; int n; };"), None);
+    assert_eq!(flexible("struct s { int n; char d[4]; };"), None);
+}
+
+#[test]
+fn round_trip() {
+    let file = parse(INPUT);
+    let printed = to_tokens::to_tokens(&file).to_string();
+    let reparsed = to_tokens::to_tokens(&parse(&printed)).to_string();
+    assert_eq!(printed, reparsed);
+    let flat = printed.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert!(flat.contains("unsigned kind : 3 , : 0 ;"));
+    assert!(flat.contains("_Static_assert ( KIND_BITS == 3 , \"three kind bits\" ) ;"));
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::file::File;
+use cyn::selection::SwitchLabel;
+use cyn::{to_tokens, Expr, Item, Stmnt, Switch};
+
+const INPUT: &str = r#"int f(int n, int m) {
+    switch (n) {
+    case 1:
+    case 2: n++;
+        break;
+    case 3 + 1: {
+        while (m) {
+        case 5: m--;
+        }
+    }
+    default:
+        switch (m) { case 7: default: break; }
+        if (m) case 8: return 1;
+    }
+    switch (n) case 9: n = 2;
+    return n;
+}
+"#;
+
+fn switches(file: &File) -> Vec<&Switch> {
+    let Item::Fn(f) = &file.declarations[0] else {
+        panic!("expected function");
+    };
+    f.body
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            BlockItem::Stmnt(Stmnt::Switch(switch)) => Some(switch),
+            _ => None,
+        })
+        .collect()
+}
+
+fn labels(switch: &Switch) -> Vec<String> {
+    switch
+        .labels()
+        .into_iter()
+        .map(|label| match label {
+            SwitchLabel::Case(case) => to_tokens::to_tokens(case.value()).to_string(),
+            SwitchLabel::Default(_) => "default".to_owned(),
+        })
+        .map(|label| label.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect()
+}
+
+#[test]
+fn case_labels() {
+    let file = parse(INPUT);
+    let switches = switches(&file);
+    assert_eq!(switches.len(), 2);
+
+    let outer = switches[0];
+    assert!(matches!(*outer.stmnt, Stmnt::Block(_)));
+    assert_eq!(labels(outer), ["1", "2", "3 + 1", "5", "default", "8"]);
+    assert!(matches!(outer.case_values().nth(2), Some(Expr::Binary(_))));
+
+    let SwitchLabel::Default(default) = outer.labels()[4] else {
+        panic!("expected default label");
+    };
+    let Stmnt::Switch(inner) = &*default.stmnt else {
+        panic!("expected nested switch");
+    };
+    assert_eq!(labels(inner), ["7", "default"]);
+
+    let single = switches[1];
+    assert!(matches!(*single.stmnt, Stmnt::Case(_)));
+    assert_eq!(labels(single), ["9"]);
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = switches(&file)
+        .into_iter()
+        .map(|switch| {
+            let printed = to_tokens::to_tokens(switch).to_string();
+            printed.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "switch ( n ) { case 1 : case 2 : n ++ ; break ; \
+             case 3 + 1 : { while ( m ) { case 5 : m -- ; } } \
+             default : switch ( m ) { case 7 : default : break ; } \
+             if ( m ) case 8 : return 1 ; }",
+            "switch ( n ) case 9 : n = 2 ;",
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        (
+            "case 1: n++;",
+            "`case` label not within a switch statement",
+            1,
+        ),
+        (
+            "default: n++;",
+            "`default` label not within a switch statement",
+            1,
+        ),
+        (
+            "while (n) { case 1: break; }",
+            "`case` label not within a switch statement",
+            13,
+        ),
+        (
+            "switch (n) { case 1 n++; }",
+            "expected token ':', got 'n'",
+            21,
+        ),
+        (
+            "switch (n) { case: n++; }",
+            "expected primary expression.",
+            18,
+        ),
+        (
+            "switch (n) { case 1, 2: n++; }",
+            "expected token ':', got ','",
+            20,
+        ),
+        (
+            "switch (n n) { }",
+            "expected `)` after switch expression",
+            11,
+        ),
+    ];
+    for (body, msg, col) in cases {
+        let (err, pos) = parse_err(&format!("void f(int n) {{\n{body}\n}}\n"));
+        assert_eq!(err, msg, "{body}");
+        assert_eq!(pos.map(|pos| (pos.row, pos.col)), Some((2, col)), "{body}");
+    }
+}
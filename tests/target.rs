@@ -0,0 +1,59 @@
+use cyn::pp::Preprocessor;
+use cyn::target::TargetProfile;
+use cyn::Dialect;
+
+const INPUT: &str = r#"
+#if __SIZEOF_LONG__ == 8
+long64
+#endif
+#if defined(__x86_64__) && __STDC_VERSION__ >= 201112L
+x86_64
+#endif
+#ifdef _WIN32
+windows
+#endif
+#if __INT_MAX__ == 0x7fffffff && __LONG_MAX__ > __INT_MAX__
+long_max
+#endif
+#ifdef linux
+gnu_linux
+#endif
+"#;
+
+fn pp(mut pp: Preprocessor, input: &str) -> String {
+    let ts = pp.preprocess(input).unwrap();
+    ts.to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn profiles() {
+    let target = |triple| TargetProfile::from_triple(triple).unwrap();
+    let iso = |triple| Preprocessor::for_target(&target(triple), Dialect::Iso);
+    assert_eq!(pp(iso("x86_64-linux-gnu"), INPUT), "long64 x86_64 long_max");
+    assert_eq!(pp(iso("x86_64-windows-msvc"), INPUT), "windows");
+    assert_eq!(pp(iso("i686-linux-gnu"), INPUT), "");
+    assert_eq!(pp(iso("aarch64-linux-gnu"), INPUT), "long64 long_max");
+    let gnu = Preprocessor::for_target(&target("i686-linux-gnu"), Dialect::Gnu);
+    assert_eq!(pp(gnu, INPUT), "gnu_linux");
+
+    let arm = TargetProfile::arm_none_eabi();
+    assert_eq!(arm.sizes.pointer, 4);
+    assert!(!arm.sizes.char_signed);
+    assert_eq!(TargetProfile::x86_64_windows_msvc().sizes.long, 4);
+    assert!(TargetProfile::from_triple("pdp11-unix").is_none());
+}
+
+#[test]
+fn command_line_definitions() {
+    let mut pp_ = Preprocessor::for_target(&TargetProfile::x86_64_linux_gnu(), Dialect::Iso);
+    pp_.define("DEBUG").unwrap();
+    pp_.define("LEVEL=3").unwrap();
+    pp_.define("SQ(x)=((x) * (x))").unwrap();
+    pp_.define("__SIZEOF_LONG__=4").unwrap();
+    pp_.undefine("__x86_64__");
+    let input = "DEBUG LEVEL SQ(2)\n#if __SIZEOF_LONG__ == 8 || defined __x86_64__\nno\n#endif\n";
+    assert_eq!(pp(pp_, input), "1 3 ( ( 2 ) * ( 2 ) )");
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::{parse, parse_err};
+use cyn::block::BlockItem;
+use cyn::declr::Derivation;
+use cyn::file::File;
+use cyn::stmnt::StmntExpr;
+use cyn::{to_tokens, Expr, FnParam, Item, Stmnt, TypeName};
+
+const INPUT: &str = r#"void f(void *p, long x) {
+    void sort(void *base, int (*)(const void *, const void *));
+    (int (*)[10])p;
+    (void (*)(int, char *))p;
+    (const char **)p;
+    _Alignof(unsigned long);
+}
+"#;
+
+fn shape(ty: &TypeName) -> String {
+    ty.derivations()
+        .iter()
+        .map(|derivation| match derivation {
+            Derivation::Pointer(_) => "*",
+            Derivation::Array { len, .. } => {
+                assert!(len.is_some());
+                "[]"
+            }
+            Derivation::Function(_) | Derivation::IdentList(_) => "()",
+        })
+        .collect()
+}
+
+fn body(file: &File) -> &[BlockItem] {
+    let Item::Fn(f) = &file.declarations[0] else {
+        panic!("expected function");
+    };
+    &f.body.items
+}
+
+fn type_names(file: &File) -> Vec<&TypeName> {
+    body(file)
+        .iter()
+        .skip(1)
+        .map(|item| match item {
+            BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => match expr.as_ref() {
+                Expr::Cast(cast) => &cast.ty,
+                Expr::Alignof(alignof) => &alignof.ty,
+                _ => panic!("expected cast or _Alignof"),
+            },
+            _ => panic!("expected expression statement"),
+        })
+        .collect()
+}
+
+#[test]
+fn abstract_declarators() {
+    let file = parse(INPUT);
+    let shapes: Vec<_> = type_names(&file).into_iter().map(shape).collect();
+    assert_eq!(shapes, ["*[]", "*()", "**", ""]);
+    assert_eq!(type_names(&file)[3].specifiers.items.len(), 2);
+}
+
+#[test]
+fn unnamed_parameters() {
+    let file = parse(INPUT);
+    let BlockItem::Declr(declr) = &body(&file)[0] else {
+        panic!("expected declaration");
+    };
+    let declarator = declr.vars.iter().next().unwrap().declarator();
+    let Some(Derivation::Function(params)) = declarator.derivations().first().copied() else {
+        panic!("expected function");
+    };
+    let params: Vec<_> = params.params.iter().collect();
+    match params[1] {
+        FnParam::Unnamed(ty) => assert_eq!(shape(ty), "*()"),
+        FnParam::Named(_) => panic!("expected unnamed parameter"),
+    }
+}
+
+#[test]
+fn printing() {
+    let file = parse(INPUT);
+    let printed: Vec<_> = type_names(&file)
+        .into_iter()
+        .map(|ty| to_tokens::to_tokens(ty).to_string().trim().to_string())
+        .collect();
+    assert_eq!(
+        printed,
+        [
+            "int ( * ) [ 10 ]",
+            "void ( * ) ( int , char * )",
+            "const char * *",
+            "unsigned long"
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    let checks = [
+        (
+            "(static int)x",
+            2,
+            "type name does not allow storage class to be specified",
+        ),
+        (
+            "sizeof(typedef int)",
+            8,
+            "type name does not allow storage class to be specified",
+        ),
+        (
+            "_Alignof(extern int)",
+            10,
+            "type name does not allow storage class to be specified",
+        ),
+        (
+            "(int inline *)x",
+            6,
+            "type name does not allow function specifier to be specified",
+        ),
+        ("(int (*p))x", 8, "expected `)` after abstract declarator"),
+        ("(int x)y", 6, "expected `)` after type name"),
+    ];
+    for (expr, col, expected) in checks {
+        let (msg, pos) = parse_err(&format!("void f(void *x) {{\n{expr};\n}}\n"));
+        assert_eq!((msg.as_str(), pos.unwrap().col), (expected, col), "{expr}");
+    }
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::{parse, parse_err, parse_with};
+use cyn::block::BlockItem;
+use cyn::stmnt::StmntExpr;
+use cyn::{Expr, Item, ParseConfig, Stmnt};
+
+fn body(item: &Item) -> &[BlockItem] {
+    match item {
+        Item::Fn(f) => &f.body.items,
+        _ => panic!("expected function"),
+    }
+}
+
+fn expr(item: &BlockItem) -> &Expr {
+    match item {
+        BlockItem::Stmnt(Stmnt::Expr(StmntExpr { expr, .. })) => expr,
+        _ => panic!("expected expression statement"),
+    }
+}
+
+#[test]
+fn declarations_and_expressions() {
+    let file = parse(
+        r#"typedef unsigned long size_t;
+size_t n;
+size_t len(size_t a, size_t *b) {
+    size_t c = a;
+    a * b;
+    return c;
+}
+"#,
+    );
+    assert!(matches!(file.declarations[1], Item::Declr(_)));
+    let Item::Fn(f) = &file.declarations[2] else {
+        panic!("expected function");
+    };
+    assert!(f.params().is_some());
+    let body = body(&file.declarations[2]);
+    assert!(matches!(body[0], BlockItem::Declr(_)));
+    assert!(matches!(expr(&body[1]), Expr::Binary(_)));
+}
+
+#[test]
+fn shadowing() {
+    let file = parse(
+        r#"typedef int T;
+int f(int a) {
+    T * p;
+    {
+        int T = 2;
+        T * a;
+    }
+    T * q;
+}
+int g(int T) {
+    T * T;
+}
+"#,
+    );
+    let body = body(&file.declarations[1]);
+    assert!(matches!(body[0], BlockItem::Declr(_)));
+    let BlockItem::Stmnt(Stmnt::Block(inner)) = &body[1] else {
+        panic!("expected block");
+    };
+    assert!(matches!(inner.items[0], BlockItem::Declr(_)));
+    assert!(matches!(expr(&inner.items[1]), Expr::Binary(_)));
+    assert!(matches!(body[2], BlockItem::Declr(_)));
+    assert!(matches!(
+        expr(&self::body(&file.declarations[2])[0]),
+        Expr::Binary(_)
+    ));
+}
+
+#[test]
+fn casts() {
+    let file = parse(
+        r#"typedef int T;
+int f(int a, int b, int *p) {
+    (T)*p;
+    (a)*b;
+}
+"#,
+    );
+    let body = body(&file.declarations[1]);
+    assert!(matches!(expr(&body[0]), Expr::Cast(_)));
+    match expr(&body[1]) {
+        Expr::Binary(binary) => assert!(matches!(*binary.lhs, Expr::Paren(_))),
+        _ => panic!("expected multiplication"),
+    }
+}
+
+#[test]
+fn labels() {
+    let file = parse(
+        r#"typedef int T;
+int f(int a) {
+T:
+    a++;
+    if (a) goto T;
+    T * p;
+}
+"#,
+    );
+    let body = body(&file.declarations[1]);
+    let BlockItem::Stmnt(Stmnt::Label(label)) = &body[0] else {
+        panic!("expected label");
+    };
+    assert_eq!(label.ident.as_str(), "T");
+    assert!(matches!(body[2], BlockItem::Declr(_)));
+}
+
+#[test]
+fn configured_names() {
+    let input = "FILE *f;\n";
+    assert_eq!(parse_err(input).0, "expected at least one specifier");
+    let config = ParseConfig {
+        typedef_names: vec!["FILE".to_string()],
+        ..Default::default()
+    };
+    let file = parse_with(input, config);
+    assert!(matches!(file.declarations[0], Item::Declr(_)));
+}